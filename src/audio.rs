@@ -0,0 +1,524 @@
+use crate::error::{BtwError, Result};
+use crate::porcupine::Porcupine;
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use crossbeam_channel::{Receiver, Sender};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// The capture sample formats we know how to normalize down to the `i16`
+/// buffer Porcupine/ASR expect, mirroring cpal's own sample-format mapping
+/// (U8, S16, S24-in-32, F32 cover the overwhelming majority of real mics).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureSample {
+    U8,
+    S16,
+    S24In32,
+    F32,
+}
+
+impl CaptureSample {
+    /// Bits-per-sample as it should be reported in a WAV header once
+    /// converted to the i16 buffer (always 16 — conversion normalizes).
+    pub fn output_bits_per_sample() -> u16 {
+        16
+    }
+
+    fn from_u8(data: &[u8]) -> Vec<i16> {
+        data.iter().map(|&s| ((s as i16) - 128) << 8).collect()
+    }
+
+    fn from_s24_in_32(data: &[i32]) -> Vec<i16> {
+        // The low 8 bits are padding; shift the real 24-bit value down to i16.
+        data.iter().map(|&s| (s >> 16) as i16).collect()
+    }
+
+    fn from_f32(data: &[f32]) -> Vec<i16> {
+        data.iter().map(|&s| (s.clamp(-1.0, 1.0) * i16::MAX as f32) as i16).collect()
+    }
+}
+
+/// Picks an input device by configured name, falling back to the system
+/// default. Mirrors the cpal ALSA pattern: try the named device, and if
+/// it's unavailable (ENODEV/EBUSY) fall back rather than aborting `run()`.
+fn select_device(host: &cpal::Host, configured: Option<&str>) -> Result<cpal::Device> {
+    if let Some(name) = configured.filter(|n| !n.eq_ignore_ascii_case("default")) {
+        let found = host
+            .input_devices()
+            .map_err(|e| BtwError::AudioDeviceError { message: format!("enumerate input devices: {}", e) })?
+            .find(|d| d.name().map(|n| n == name).unwrap_or(false));
+        if let Some(device) = found {
+            return Ok(device);
+        }
+        eprintln!("audio: configured input device '{}' unavailable, falling back to default", name);
+    }
+
+    host.default_input_device()
+        .ok_or_else(|| BtwError::AudioDeviceError { message: "no default input device available".into() })
+}
+
+/// Lists the names of all available input devices, so `config` can present
+/// a human-pickable list instead of a raw ALSA/CoreAudio string.
+pub fn list_input_devices() -> Result<Vec<String>> {
+    let host = cpal::default_host();
+    let devices = host
+        .input_devices()
+        .map_err(|e| BtwError::AudioDeviceError { message: format!("enumerate input devices: {}", e) })?;
+    Ok(devices.filter_map(|d| d.name().ok()).collect())
+}
+
+/// Opens the configured capture device (or the system default), negotiates
+/// a supported sample rate/format, and feeds fixed-size `i16` frames sized
+/// to Porcupine's `frame_length()` into the returned channel. Resamples to
+/// Porcupine's required rate when the hardware doesn't natively support it.
+///
+/// If `BTWD_AUDIO_SOURCE` is set (`file:<path>` or `testsig:<spec>`), the
+/// real microphone is bypassed entirely and frames are replayed/synthesized
+/// instead, so the VAD -> ASR -> intent -> executor pipeline can be driven
+/// deterministically in integration tests.
+pub fn start_listening(
+    porcupine: &Porcupine,
+    audio_cfg: &crate::config::AudioCfg,
+) -> Result<(JoinHandle<()>, Receiver<Vec<i16>>)> {
+    let target_rate = porcupine.sample_rate();
+    let frame_length = porcupine.frame_length();
+
+    if let Ok(raw) = std::env::var("BTWD_AUDIO_SOURCE") {
+        if !raw.trim().is_empty() {
+            return start_synthetic_source(&raw, target_rate, frame_length);
+        }
+    }
+
+    start_device_capture(porcupine, audio_cfg)
+}
+
+fn start_device_capture(
+    porcupine: &Porcupine,
+    audio_cfg: &crate::config::AudioCfg,
+) -> Result<(JoinHandle<()>, Receiver<Vec<i16>>)> {
+    let host = cpal::default_host();
+    let device = select_device(&host, audio_cfg.input_device.as_deref())?;
+    let device_name = device.name().unwrap_or_else(|_| "unknown".to_string());
+    eprintln!("audio: using input device '{}'", device_name);
+
+    let target_rate = porcupine.sample_rate();
+    let frame_length = porcupine.frame_length();
+
+    let supported: Vec<_> = device
+        .supported_input_configs()
+        .map_err(|e| BtwError::AudioDeviceError { message: format!("query supported formats: {}", e) })?
+        .collect();
+    if supported.is_empty() {
+        return Err(BtwError::AudioDeviceError {
+            message: format!("device '{}' exposes no supported input formats", device_name),
+        });
+    }
+
+    let chosen = supported
+        .iter()
+        .find(|c| c.min_sample_rate().0 <= target_rate && target_rate <= c.max_sample_rate().0)
+        .cloned()
+        .unwrap_or_else(|| supported[0].clone());
+
+    let sample_rate = if chosen.min_sample_rate().0 <= target_rate && target_rate <= chosen.max_sample_rate().0 {
+        target_rate
+    } else {
+        chosen.max_sample_rate().0
+    };
+    let needs_resample = sample_rate != target_rate;
+    if needs_resample {
+        eprintln!("audio: device rate {} != porcupine rate {}, resampling", sample_rate, target_rate);
+    }
+
+    let config = chosen.with_sample_rate(cpal::SampleRate(sample_rate)).config();
+    let sample_format = chosen.sample_format();
+
+    let (tx, rx) = crossbeam_channel::unbounded::<Vec<i16>>();
+
+    let handle = std::thread::Builder::new()
+        .name("btwd-audio".into())
+        .spawn(move || run_capture_stream(device, config, sample_format, tx, sample_rate, target_rate, frame_length))
+        .map_err(|e| BtwError::AudioDeviceError { message: format!("spawn audio thread: {}", e) })?;
+
+    Ok((handle, rx))
+}
+
+fn run_capture_stream(
+    device: cpal::Device,
+    config: cpal::StreamConfig,
+    sample_format: cpal::SampleFormat,
+    tx: Sender<Vec<i16>>,
+    sample_rate: u32,
+    target_rate: u32,
+    frame_length: u32,
+) {
+    let err_fn = |e| eprintln!("audio: stream error: {}", e);
+    let mut residual: Vec<i16> = Vec::new();
+
+    let stream = match sample_format {
+        cpal::SampleFormat::U8 => device.build_input_stream(
+            &config,
+            move |data: &[u8], _: &cpal::InputCallbackInfo| {
+                let converted = CaptureSample::from_u8(data);
+                emit_frames(&tx, &converted, sample_rate, target_rate, frame_length, &mut residual)
+            },
+            err_fn,
+            None,
+        ),
+        cpal::SampleFormat::I16 => device.build_input_stream(
+            &config,
+            move |data: &[i16], _: &cpal::InputCallbackInfo| {
+                emit_frames(&tx, data, sample_rate, target_rate, frame_length, &mut residual)
+            },
+            err_fn,
+            None,
+        ),
+        cpal::SampleFormat::U16 => device.build_input_stream(
+            &config,
+            move |data: &[u16], _: &cpal::InputCallbackInfo| {
+                let converted: Vec<i16> = data.iter().map(|&s| (s as i32 - 32768) as i16).collect();
+                emit_frames(&tx, &converted, sample_rate, target_rate, frame_length, &mut residual)
+            },
+            err_fn,
+            None,
+        ),
+        cpal::SampleFormat::I32 => device.build_input_stream(
+            &config,
+            move |data: &[i32], _: &cpal::InputCallbackInfo| {
+                let converted = CaptureSample::from_s24_in_32(data);
+                emit_frames(&tx, &converted, sample_rate, target_rate, frame_length, &mut residual)
+            },
+            err_fn,
+            None,
+        ),
+        cpal::SampleFormat::F32 => device.build_input_stream(
+            &config,
+            move |data: &[f32], _: &cpal::InputCallbackInfo| {
+                let converted = CaptureSample::from_f32(data);
+                emit_frames(&tx, &converted, sample_rate, target_rate, frame_length, &mut residual)
+            },
+            err_fn,
+            None,
+        ),
+        other => {
+            eprintln!("audio: unsupported sample format {:?}", other);
+            return;
+        }
+    };
+
+    let stream = match stream {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("audio: failed to build input stream: {}", e);
+            return;
+        }
+    };
+
+    if let Err(e) = stream.play() {
+        eprintln!("audio: failed to start stream: {}", e);
+        return;
+    }
+
+    // cpal drives the stream callbacks on its own thread(s); just keep this
+    // thread (and the stream, which must stay alive) parked for its lifetime.
+    loop {
+        std::thread::park();
+    }
+}
+
+/// Naive linear resampler. Good enough for voice-command audio, where
+/// landing on Porcupine's required rate matters more than exact fidelity.
+fn resample_linear(input: &[i16], from_rate: u32, to_rate: u32) -> Vec<i16> {
+    if from_rate == to_rate || input.is_empty() {
+        return input.to_vec();
+    }
+    let ratio = to_rate as f64 / from_rate as f64;
+    let out_len = ((input.len() as f64) * ratio).round() as usize;
+    (0..out_len)
+        .map(|i| {
+            let src_pos = i as f64 / ratio;
+            let idx = src_pos.floor() as usize;
+            let frac = src_pos - idx as f64;
+            let a = input.get(idx).copied().unwrap_or(0) as f64;
+            let b = input.get(idx + 1).copied().unwrap_or(a as i16) as f64;
+            (a + (b - a) * frac) as i16
+        })
+        .collect()
+}
+
+/// Encodes mono `i16` samples as a standard RIFF/WAV file so debug captures
+/// open in any player, instead of the headerless `.pcm16` dumps this used
+/// to write.
+pub fn encode_wav(samples: &[i16], sample_rate: u32) -> Vec<u8> {
+    let channels: u16 = 1;
+    let bits_per_sample: u16 = CaptureSample::output_bits_per_sample();
+    let block_align = channels * (bits_per_sample / 8);
+    let byte_rate = sample_rate * block_align as u32;
+    let data_len = (samples.len() * 2) as u32;
+
+    let mut out = Vec::with_capacity(44 + data_len as usize);
+    out.extend_from_slice(b"RIFF");
+    out.extend_from_slice(&(36 + data_len).to_le_bytes());
+    out.extend_from_slice(b"WAVE");
+
+    out.extend_from_slice(b"fmt ");
+    out.extend_from_slice(&16u32.to_le_bytes()); // PCM fmt chunk size
+    out.extend_from_slice(&1u16.to_le_bytes()); // PCM format tag
+    out.extend_from_slice(&channels.to_le_bytes());
+    out.extend_from_slice(&sample_rate.to_le_bytes());
+    out.extend_from_slice(&byte_rate.to_le_bytes());
+    out.extend_from_slice(&block_align.to_le_bytes());
+    out.extend_from_slice(&bits_per_sample.to_le_bytes());
+
+    out.extend_from_slice(b"data");
+    out.extend_from_slice(&data_len.to_le_bytes());
+    for s in samples {
+        out.extend_from_slice(&s.to_le_bytes());
+    }
+
+    out
+}
+
+/// Resamples (if needed) and chunks `data` into exact `frame_length`-sized
+/// frames, same as the live capture path. `pub(crate)` so other ingestion
+/// paths (e.g. `remote`'s Opus-decoded audio) can feed the same pipeline.
+///
+/// `residual` carries the sub-`frame_length` remainder across calls instead
+/// of discarding it: a single call's `data` isn't guaranteed to be a
+/// multiple of `frame_length` (a 20ms Opus packet at 16kHz is 320 samples,
+/// smaller than Porcupine's usual 512-sample frame), so chunking each call
+/// in isolation can drop every sample of a call whose buffer never reaches
+/// a full frame on its own. Callers own `residual` and pass the same
+/// `Vec` back in on every call for a given stream/connection.
+pub(crate) fn emit_frames(
+    tx: &Sender<Vec<i16>>,
+    data: &[i16],
+    sample_rate: u32,
+    target_rate: u32,
+    frame_length: u32,
+    residual: &mut Vec<i16>,
+) {
+    let samples = if sample_rate != target_rate {
+        resample_linear(data, sample_rate, target_rate)
+    } else {
+        data.to_vec()
+    };
+    residual.extend_from_slice(&samples);
+
+    let frame_length = frame_length as usize;
+    let mut offset = 0;
+    while residual.len() - offset >= frame_length {
+        let _ = tx.send(residual[offset..offset + frame_length].to_vec());
+        offset += frame_length;
+    }
+    residual.drain(0..offset);
+}
+
+/// How far two adjacent samples across a frame boundary can jump before
+/// we flag it as a discontinuity (clipping/splice artifact) rather than
+/// ordinary waveform movement.
+const DISCONTINUITY_THRESHOLD: i32 = 12_000;
+
+/// Logs a warning when the first sample of `frame` jumps too far from the
+/// last sample handed to the previous call — lets integration tests using
+/// `testsig:`/`file:` sources catch clicks introduced at segment or chunk
+/// boundaries.
+fn check_discontinuity(last_sample: &mut Option<i16>, frame: &[i16]) {
+    if let (Some(prev), Some(&first)) = (*last_sample, frame.first()) {
+        let delta = (first as i32 - prev as i32).abs();
+        if delta > DISCONTINUITY_THRESHOLD {
+            eprintln!("audio: frame discontinuity detected (delta={})", delta);
+        }
+    }
+    if let Some(&last) = frame.last() {
+        *last_sample = Some(last);
+    }
+}
+
+/// Minimal RIFF/WAVE PCM decoder, the inverse of `encode_wav`. Only handles
+/// mono or interleaved 16-bit integer PCM, which is all our debug dumps and
+/// test fixtures produce.
+fn decode_wav(bytes: &[u8]) -> Result<(u32, Vec<i16>)> {
+    let err = || BtwError::AudioDeviceError { message: "malformed WAV file".into() };
+    if bytes.len() < 44 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err(err());
+    }
+
+    let mut pos = 12;
+    let mut sample_rate: Option<u32> = None;
+    let mut channels: u16 = 1;
+    let mut bits_per_sample: u16 = 16;
+    let mut data: Option<&[u8]> = None;
+
+    while pos + 8 <= bytes.len() {
+        let chunk_id = &bytes[pos..pos + 4];
+        let chunk_len = u32::from_le_bytes(bytes[pos + 4..pos + 8].try_into().map_err(|_| err())?) as usize;
+        let body_start = pos + 8;
+        let body_end = body_start.checked_add(chunk_len).ok_or_else(err)?;
+        if body_end > bytes.len() {
+            break;
+        }
+        let body = &bytes[body_start..body_end];
+
+        if chunk_id == b"fmt " {
+            if body.len() < 16 {
+                return Err(err());
+            }
+            channels = u16::from_le_bytes(body[2..4].try_into().map_err(|_| err())?);
+            sample_rate = Some(u32::from_le_bytes(body[4..8].try_into().map_err(|_| err())?));
+            bits_per_sample = u16::from_le_bytes(body[14..16].try_into().map_err(|_| err())?);
+        } else if chunk_id == b"data" {
+            data = Some(body);
+        }
+
+        pos = body_end + (chunk_len % 2); // chunks are word-aligned
+    }
+
+    let sample_rate = sample_rate.ok_or_else(err)?;
+    let data = data.ok_or_else(err)?;
+    if bits_per_sample != 16 {
+        return Err(BtwError::AudioDeviceError {
+            message: format!("unsupported WAV bit depth {} (only 16-bit PCM is supported)", bits_per_sample),
+        });
+    }
+
+    let mut samples: Vec<i16> = data
+        .chunks_exact(2)
+        .map(|b| i16::from_le_bytes([b[0], b[1]]))
+        .collect();
+
+    // Downmix to mono by averaging channels, since Porcupine/VAD expect mono.
+    if channels > 1 {
+        samples = samples
+            .chunks(channels as usize)
+            .map(|frame| (frame.iter().map(|&s| s as i32).sum::<i32>() / frame.len() as i32) as i16)
+            .collect();
+    }
+
+    Ok((sample_rate, samples))
+}
+
+fn start_synthetic_source(raw: &str, target_rate: u32, frame_length: u32) -> Result<(JoinHandle<()>, Receiver<Vec<i16>>)> {
+    if let Some(path) = raw.strip_prefix("file:") {
+        eprintln!("audio: BTWD_AUDIO_SOURCE=file, replaying '{}'", path);
+        start_file_source(std::path::PathBuf::from(path), target_rate, frame_length)
+    } else if let Some(spec) = raw.strip_prefix("testsig:") {
+        eprintln!("audio: BTWD_AUDIO_SOURCE=testsig, generating '{}'", spec);
+        start_testsig_source(spec.to_string(), target_rate, frame_length)
+    } else {
+        Err(BtwError::AudioDeviceError {
+            message: format!("unrecognized BTWD_AUDIO_SOURCE '{}' (expected 'file:<path>' or 'testsig:<spec>')", raw),
+        })
+    }
+}
+
+fn start_file_source(path: std::path::PathBuf, target_rate: u32, frame_length: u32) -> Result<(JoinHandle<()>, Receiver<Vec<i16>>)> {
+    let bytes = std::fs::read(&path).map_err(|e| BtwError::ReadError { path: path.clone(), source: e })?;
+
+    let (sample_rate, samples) = if bytes.len() >= 4 && &bytes[0..4] == b"RIFF" {
+        decode_wav(&bytes)?
+    } else {
+        // Raw headerless PCM: assumed little-endian i16 mono at the target rate.
+        let samples = bytes
+            .chunks_exact(2)
+            .map(|b| i16::from_le_bytes([b[0], b[1]]))
+            .collect();
+        (target_rate, samples)
+    };
+
+    let (tx, rx) = crossbeam_channel::unbounded::<Vec<i16>>();
+    let frame_ms = (frame_length as f64) * 1000.0 / target_rate as f64;
+
+    let handle = std::thread::Builder::new()
+        .name("btwd-audio-file".into())
+        .spawn(move || {
+            let resampled = if sample_rate != target_rate {
+                resample_linear(&samples, sample_rate, target_rate)
+            } else {
+                samples
+            };
+
+            let mut last_sample: Option<i16> = None;
+            for chunk in resampled.chunks(frame_length as usize) {
+                if chunk.len() != frame_length as usize {
+                    break; // drop a short trailing partial frame, same as live capture
+                }
+                check_discontinuity(&mut last_sample, chunk);
+                if tx.send(chunk.to_vec()).is_err() {
+                    return;
+                }
+                std::thread::sleep(Duration::from_millis(frame_ms as u64));
+            }
+            eprintln!("audio: file source exhausted, closing stream");
+        })
+        .map_err(|e| BtwError::AudioDeviceError { message: format!("spawn audio thread: {}", e) })?;
+
+    Ok((handle, rx))
+}
+
+/// One segment of a `testsig:` specification, e.g. `sine:440:2000` (440Hz
+/// for 2000ms) or `silence:500` (500ms of silence).
+enum TestSegment {
+    Sine { freq_hz: f64, ms: u64 },
+    Silence { ms: u64 },
+}
+
+fn parse_testsig_spec(spec: &str) -> Result<Vec<TestSegment>> {
+    let err = |s: &str| BtwError::AudioDeviceError { message: format!("invalid testsig segment '{}'", s) };
+    spec.split(',')
+        .map(|segment| {
+            let parts: Vec<&str> = segment.split(':').collect();
+            match parts.as_slice() {
+                ["sine", freq, ms] => Ok(TestSegment::Sine {
+                    freq_hz: freq.parse().map_err(|_| err(segment))?,
+                    ms: ms.parse().map_err(|_| err(segment))?,
+                }),
+                ["silence", ms] => Ok(TestSegment::Silence { ms: ms.parse().map_err(|_| err(segment))? }),
+                _ => Err(err(segment)),
+            }
+        })
+        .collect()
+}
+
+fn start_testsig_source(spec: String, target_rate: u32, frame_length: u32) -> Result<(JoinHandle<()>, Receiver<Vec<i16>>)> {
+    let segments = parse_testsig_spec(&spec)?;
+    let (tx, rx) = crossbeam_channel::unbounded::<Vec<i16>>();
+    let frame_ms = (frame_length as f64) * 1000.0 / target_rate as f64;
+
+    let handle = std::thread::Builder::new()
+        .name("btwd-audio-testsig".into())
+        .spawn(move || {
+            let mut last_sample: Option<i16> = None;
+            for segment in &segments {
+                let (freq_hz, duration_ms) = match *segment {
+                    TestSegment::Sine { freq_hz, ms } => (Some(freq_hz), ms),
+                    TestSegment::Silence { ms } => (None, ms),
+                };
+
+                let total_samples = ((duration_ms as f64 / 1000.0) * target_rate as f64).round() as usize;
+                let samples: Vec<i16> = (0..total_samples)
+                    .map(|i| match freq_hz {
+                        Some(freq_hz) => {
+                            let t = i as f64 / target_rate as f64;
+                            (((2.0 * std::f64::consts::PI * freq_hz * t).sin()) * (i16::MAX as f64 * 0.8)) as i16
+                        }
+                        None => 0,
+                    })
+                    .collect();
+
+                for chunk in samples.chunks(frame_length as usize) {
+                    if chunk.len() != frame_length as usize {
+                        break;
+                    }
+                    check_discontinuity(&mut last_sample, chunk);
+                    if tx.send(chunk.to_vec()).is_err() {
+                        return;
+                    }
+                    std::thread::sleep(Duration::from_millis(frame_ms as u64));
+                }
+            }
+            eprintln!("audio: testsig source exhausted, closing stream");
+        })
+        .map_err(|e| BtwError::AudioDeviceError { message: format!("spawn audio thread: {}", e) })?;
+
+    Ok((handle, rx))
+}
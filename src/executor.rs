@@ -0,0 +1,242 @@
+use crate::error::Result;
+use crate::intent::IntentResult;
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+pub struct ExecutionCfg {
+    pub confirmation_timeout_seconds: u64,
+    pub dry_run: bool,
+    /// Minimum gap between accepted dispatches of the *same* command_id, so
+    /// a wake-word repeated over noisy ASR doesn't enqueue duplicates.
+    pub action_throttle_ms: u64,
+    /// What to do with a new dangerous command while one is already pending
+    /// confirmation (borrowed from watchexec's on-busy semantics).
+    pub on_busy: OnBusyPolicy,
+}
+
+/// What happens when a dangerous command arrives while another is already
+/// pending confirmation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnBusyPolicy {
+    /// Hold the new command and start it once the current one resolves.
+    Queue,
+    /// Drop the current pending command and start the new one immediately.
+    Restart,
+    /// Drop the new command; the current pending one is unaffected.
+    Ignore,
+}
+
+#[derive(Debug)]
+pub enum ExecStatus {
+    Executed { command_id: String },
+    Pending { request_id: String },
+    Confirmed { command_id: String },
+    Cancelled { request_id: String },
+    TimedOut { request_id: String },
+    /// A dangerous command arrived while another was pending and `on_busy`
+    /// is `Queue`; it will become the next pending command once the
+    /// current one resolves.
+    Queued { command_id: String },
+    Ignored,
+    Error(String),
+}
+
+struct PendingCommand {
+    request_id: String,
+    command_id: String,
+    parameters: serde_json::Value,
+    created_at: Instant,
+}
+
+/// Runs allow-listed commands, gating anything flagged dangerous behind a
+/// single in-flight confirmation. Only one command can be pending at a
+/// time; a second dangerous request while one is outstanding is handled by
+/// `cfg.on_busy`, and repeated dispatches of the same command within
+/// `cfg.action_throttle_ms` are debounced away.
+pub struct Executor {
+    cfg: ExecutionCfg,
+    commands: Vec<crate::commands::CommandSpec>,
+    pending: Option<PendingCommand>,
+    /// Set only under `OnBusyPolicy::Queue`; started once `pending` clears.
+    queued: Option<(String, serde_json::Value)>,
+    last_dispatch: HashMap<String, Instant>,
+    next_request_id: u64,
+}
+
+impl Executor {
+    pub fn new_from_path(path: &Path, cfg: ExecutionCfg) -> Result<Self> {
+        let raw = std::fs::read_to_string(path).map_err(|e| crate::error::BtwError::ReadError {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+        let commands = crate::commands::parse_commands_json(&raw).map_err(|msg| crate::error::BtwError::ParseError {
+            path: path.to_path_buf(),
+            kind: "json",
+            message: msg,
+        })?;
+        Ok(Self {
+            cfg,
+            commands,
+            pending: None,
+            queued: None,
+            last_dispatch: HashMap::new(),
+            next_request_id: 0,
+        })
+    }
+
+    pub fn has_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    pub fn pending_request_id(&self) -> Option<&str> {
+        self.pending.as_ref().map(|p| p.request_id.as_str())
+    }
+
+    fn alloc_request_id(&mut self) -> String {
+        self.next_request_id += 1;
+        format!("req-{}", self.next_request_id)
+    }
+
+    /// Dispatches a routed intent: executes immediately, or — if it's
+    /// flagged dangerous (or the caller forced `requires_confirmation`) —
+    /// stashes it as pending until confirmed/cancelled/timed out.
+    pub fn handle_intent(&mut self, result: &IntentResult) -> ExecStatus {
+        let Some(command_id) = result.command_id.clone() else {
+            return ExecStatus::Ignored;
+        };
+
+        if self.throttled_and_mark(&command_id) {
+            eprintln!(
+                "executor: '{}' throttled (repeated within {}ms)",
+                command_id, self.cfg.action_throttle_ms
+            );
+            return ExecStatus::Ignored;
+        }
+
+        if result.requires_confirmation || result.dangerous {
+            if self.pending.is_some() {
+                return self.handle_busy(command_id, result.parameters.clone());
+            }
+            return self.start_pending(command_id, result.parameters.clone());
+        }
+
+        self.run_command(&command_id, &result.parameters)
+    }
+
+    /// Debounces repeated dispatches of the same command_id arriving faster
+    /// than `action_throttle_ms` apart (e.g. ASR re-triggering on the same
+    /// utterance). Returns `true` (and does not update the timestamp) if
+    /// this dispatch should be dropped.
+    fn throttled_and_mark(&mut self, command_id: &str) -> bool {
+        let now = Instant::now();
+        let throttle = Duration::from_millis(self.cfg.action_throttle_ms);
+        if let Some(&last) = self.last_dispatch.get(command_id) {
+            if now.duration_since(last) < throttle {
+                return true;
+            }
+        }
+        self.last_dispatch.insert(command_id.to_string(), now);
+        false
+    }
+
+    fn start_pending(&mut self, command_id: String, parameters: serde_json::Value) -> ExecStatus {
+        let request_id = self.alloc_request_id();
+        self.pending = Some(PendingCommand {
+            request_id: request_id.clone(),
+            command_id,
+            parameters,
+            created_at: Instant::now(),
+        });
+        ExecStatus::Pending { request_id }
+    }
+
+    /// Applies `cfg.on_busy` when a dangerous command arrives while one is
+    /// already pending confirmation.
+    fn handle_busy(&mut self, command_id: String, parameters: serde_json::Value) -> ExecStatus {
+        match self.cfg.on_busy {
+            OnBusyPolicy::Ignore => {
+                eprintln!("executor: on_busy=ignore, dropping '{}'", command_id);
+                ExecStatus::Ignored
+            }
+            OnBusyPolicy::Restart => {
+                eprintln!("executor: on_busy=restart, replacing pending with '{}'", command_id);
+                self.start_pending(command_id, parameters)
+            }
+            OnBusyPolicy::Queue => {
+                eprintln!("executor: on_busy=queue, queuing '{}' behind current pending", command_id);
+                self.queued = Some((command_id.clone(), parameters));
+                ExecStatus::Queued { command_id }
+            }
+        }
+    }
+
+    /// Starts the queued command (if any) once `pending` has just cleared.
+    fn advance_queue(&mut self) {
+        if let Some((command_id, parameters)) = self.queued.take() {
+            eprintln!("executor: starting queued command '{}'", command_id);
+            self.start_pending(command_id, parameters);
+        }
+    }
+
+    fn run_command(&self, command_id: &str, parameters: &serde_json::Value) -> ExecStatus {
+        let Some(spec) = self.commands.iter().find(|c| c.id == command_id) else {
+            return ExecStatus::Error(format!("unknown command_id '{}'", command_id));
+        };
+
+        if self.cfg.dry_run {
+            eprintln!("executor: dry_run, would execute '{}' with {}", spec.id, parameters);
+            return ExecStatus::Executed { command_id: spec.id.clone() };
+        }
+
+        match spec.run(parameters) {
+            Ok(()) => ExecStatus::Executed { command_id: spec.id.clone() },
+            Err(e) => ExecStatus::Error(e),
+        }
+    }
+
+    pub fn confirm_pending(&mut self) -> ExecStatus {
+        let Some(pending) = self.pending.take() else {
+            return ExecStatus::Ignored;
+        };
+        let status = self.run_command(&pending.command_id, &pending.parameters);
+        self.advance_queue();
+        match status {
+            ExecStatus::Executed { command_id } => ExecStatus::Confirmed { command_id },
+            other => other,
+        }
+    }
+
+    pub fn cancel_pending(&mut self, reason: &str) -> ExecStatus {
+        let Some(pending) = self.pending.take() else {
+            return ExecStatus::Ignored;
+        };
+        eprintln!("executor: cancelled '{}' ({})", pending.command_id, reason);
+        self.advance_queue();
+        ExecStatus::Cancelled { request_id: pending.request_id }
+    }
+
+    /// Routes free-form confirmation text ("yes"/"no"/...) to confirm/cancel.
+    pub fn handle_confirmation_text(&mut self, normalized: &str) -> ExecStatus {
+        match normalized {
+            "yes" | "confirm" | "do it" => self.confirm_pending(),
+            "no" | "cancel" | "stop" => self.cancel_pending("user said no"),
+            _ => ExecStatus::Ignored,
+        }
+    }
+
+    /// Expires a pending confirmation once it's outlived `confirmation_timeout_seconds`.
+    pub fn handle_tick(&mut self, now: Instant) -> Option<ExecStatus> {
+        let timeout = Duration::from_secs(self.cfg.confirmation_timeout_seconds);
+        if let Some(pending) = &self.pending {
+            if now.duration_since(pending.created_at) >= timeout {
+                let request_id = pending.request_id.clone();
+                eprintln!("executor: confirmation timed out for '{}'", request_id);
+                self.pending = None;
+                self.advance_queue();
+                return Some(ExecStatus::TimedOut { request_id });
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,93 @@
+//! Shared outbound HTTP client for the web search providers. Building a
+//! `reqwest::blocking::Client` per query throws away connection pooling for
+//! no reason, so the factory here builds one from the first `HttpClientCfg`
+//! it sees and every later call reuses it.
+//!
+//! TLS backend is chosen at compile time via Cargo features on `reqwest`
+//! (`default-tls`, `rustls-tls-webpki-roots`, `rustls-tls-native-roots`,
+//! `native-tls-vendored`) so a minimal Arch install without OpenSSL can
+//! still build the daemon by picking a rustls variant.
+
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// HTTP-level knobs shared by the search providers: a connect timeout
+/// distinct from the overall request timeout, and how many transient
+/// failures to retry before giving up.
+#[derive(Debug, Clone)]
+pub struct HttpClientCfg {
+    pub connect_timeout_ms: u64,
+    pub request_timeout_ms: u64,
+    pub max_retries: u32,
+}
+
+impl Default for HttpClientCfg {
+    fn default() -> Self {
+        Self { connect_timeout_ms: 5_000, request_timeout_ms: 20_000, max_retries: 3 }
+    }
+}
+
+/// Returns the process-wide shared client, building it from `cfg` on first
+/// use. Later callers reuse that same client (and its connection pool)
+/// regardless of what `cfg` they pass in.
+pub fn shared_client(cfg: &HttpClientCfg) -> Result<&'static reqwest::blocking::Client, String> {
+    static CLIENT: OnceLock<reqwest::blocking::Client> = OnceLock::new();
+    if let Some(client) = CLIENT.get() {
+        return Ok(client);
+    }
+
+    let built = reqwest::blocking::Client::builder()
+        .connect_timeout(Duration::from_millis(cfg.connect_timeout_ms))
+        .timeout(Duration::from_millis(cfg.request_timeout_ms))
+        .build()
+        .map_err(|e| format!("http client build: {}", e))?;
+
+    Ok(CLIENT.get_or_init(|| built))
+}
+
+/// Sends a request, retrying transient failures — connect errors, timeouts,
+/// and HTTP 429/5xx responses — up to `cfg.max_retries` times on
+/// `net::Backoff`'s exponential schedule, the same retry idiom `llm`'s
+/// provider client uses for its own requests.
+pub fn send_with_retry(
+    builder: &reqwest::blocking::RequestBuilder,
+    cfg: &HttpClientCfg,
+) -> Result<reqwest::blocking::Response, String> {
+    let mut backoff = crate::net::Backoff::new();
+    for attempt in 0..=cfg.max_retries {
+        // Our request bodies are always buffered JSON/query params, so this is always Some.
+        let attempt_builder = builder.try_clone().ok_or("request not cloneable for retry")?;
+        match attempt_builder.send() {
+            Ok(resp) if attempt < cfg.max_retries && is_transient_status(resp.status()) => {
+                let delay = backoff.next_delay();
+                eprintln!("http: transient status {}, retrying in {:?}", resp.status(), delay);
+                std::thread::sleep(delay);
+            }
+            Ok(resp) => return Ok(resp),
+            Err(e) if attempt < cfg.max_retries && (e.is_connect() || e.is_timeout()) => {
+                let delay = backoff.next_delay();
+                eprintln!(
+                    "http: transient error (connect={} timeout={} source={}), retrying in {:?}",
+                    e.is_connect(),
+                    e.is_timeout(),
+                    e,
+                    delay
+                );
+                std::thread::sleep(delay);
+            }
+            Err(e) => {
+                return Err(format!(
+                    "http error: connect={} timeout={} source={}",
+                    e.is_connect(),
+                    e.is_timeout(),
+                    e
+                ))
+            }
+        }
+    }
+    Err("http error: exhausted retries".into())
+}
+
+fn is_transient_status(status: reqwest::StatusCode) -> bool {
+    status.as_u16() == 429 || status.is_server_error()
+}
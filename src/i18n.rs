@@ -0,0 +1,91 @@
+//! Minimal Fluent-backed localization for the handful of user-facing
+//! strings `search` and `tts` need to speak/display in the user's
+//! configured locale, rather than hardcoding American English.
+//!
+//! There's no live locale switching: `init` picks a bundle once at
+//! startup from `cfg.locale`, and `active` hands out read-only access to
+//! it for the rest of the process's life, the same one-shot-then-OnceLock
+//! pattern `http`'s shared client and `search`'s caches use.
+
+use fluent_bundle::{FluentArgs, FluentBundle, FluentResource};
+use std::sync::OnceLock;
+use unic_langid::LanguageIdentifier;
+
+const EN_US: &str = include_str!("../locales/en-US.ftl");
+const ES_ES: &str = include_str!("../locales/es-ES.ftl");
+
+const DEFAULT_LOCALE: &str = "en-US";
+
+fn resource_for(locale: &str) -> (&'static str, &'static str) {
+    match locale {
+        "es-ES" | "es" => ("es-ES", ES_ES),
+        _ => (DEFAULT_LOCALE, EN_US),
+    }
+}
+
+/// A loaded Fluent bundle for one locale, plus the BCP-47 code it was
+/// loaded for (what `tts::SpeakOptions::lang` wants).
+pub struct Locale {
+    code: String,
+    bundle: FluentBundle<FluentResource>,
+}
+
+impl Locale {
+    fn load(requested: &str) -> Self {
+        let (code, ftl) = resource_for(requested);
+        let langid: LanguageIdentifier = code.parse().expect("built-in locale codes are valid");
+        let resource = FluentResource::try_new(ftl.to_string())
+            .unwrap_or_else(|(_, errs)| panic!("built-in {} resource has syntax errors: {:?}", code, errs));
+
+        let mut bundle = FluentBundle::new(vec![langid]);
+        // These strings are spoken by `tts::speak_async` and fed back to the
+        // LLM as part of `knowledge-check-prompt`'s `{ $sentinel }`, not just
+        // displayed — Fluent's default bidi isolation would wrap
+        // interpolated args in invisible U+2068/U+2069 marks, corrupting
+        // both the TTS output and the sentinel the model is told to echo.
+        bundle.set_use_isolating(false);
+        bundle
+            .add_resource(resource)
+            .unwrap_or_else(|errs| panic!("built-in {} resource has duplicate messages: {:?}", code, errs));
+
+        Self { code: code.to_string(), bundle }
+    }
+
+    /// BCP-47 code of the loaded locale, for passing to `tts::speak_async`.
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    /// Looks up `id` and formats it with `args`, falling back to the bare
+    /// message id if it's missing from the bundle (a missing translation
+    /// shouldn't take the whole daemon down).
+    pub fn message(&self, id: &str, args: Option<&FluentArgs>) -> String {
+        let Some(msg) = self.bundle.get_message(id) else {
+            return id.to_string();
+        };
+        let Some(pattern) = msg.value() else {
+            return id.to_string();
+        };
+        let mut errors = Vec::new();
+        let formatted = self.bundle.format_pattern(pattern, args, &mut errors);
+        if !errors.is_empty() {
+            eprintln!("i18n: formatting '{}' ({}): {:?}", id, self.code, errors);
+        }
+        formatted.into_owned()
+    }
+}
+
+static ACTIVE: OnceLock<Locale> = OnceLock::new();
+
+/// Loads the active locale from `locale_code` (e.g. `cfg.locale`). Only the
+/// first call takes effect; later calls are no-ops, matching how `search`'s
+/// process-wide caches are seeded once from whatever config first reaches them.
+pub fn init(locale_code: &str) {
+    let _ = ACTIVE.set(Locale::load(locale_code));
+}
+
+/// Returns the active locale, loading the default (`en-US`) if `init` was
+/// never called (e.g. in tests).
+pub fn active() -> &'static Locale {
+    ACTIVE.get_or_init(|| Locale::load(DEFAULT_LOCALE))
+}
@@ -6,28 +6,362 @@ pub struct LlmIntent {
     pub confidence: f32,
 }
 
+/// One tool invocation requested by the model during a `call_tools` turn.
+pub struct ToolCall {
+    pub id: String,
+    pub command_id: String,
+    pub parameters: Value,
+}
+
+/// Maximum number of tool-call round trips before we give up with an error,
+/// to avoid an unbounded back-and-forth.
+const MAX_TOOL_ITERATIONS: usize = 5;
+
 pub trait LlmClient: Send + Sync {
     fn classify_intent(&self, text: &str, commands: &[crate::intent::IntentCommand]) -> Result<LlmIntent, String>;
     fn summarize_search(&self, query: &str, snippets: &[String]) -> Result<String, String>;
     fn answer_short(&self, prompt: &str) -> Result<String, String>;
     fn tts(&self, text: &str) -> Result<Vec<u8>, String>; // return WAV bytes
+
+    /// Embeds `text` for `memory`'s local retrieval store. Errors (e.g. no
+    /// embedding model configured for this provider) are surfaced to the
+    /// caller so it can fall back to the next answer stage rather than panic.
+    fn embed(&self, text: &str) -> Result<Vec<f32>, String>;
+
+    /// Multi-step tool/function calling. Exposes each `IntentCommand` to the
+    /// provider as an OpenAI-style tool and keeps resending the conversation,
+    /// dispatching any requested tool calls via `dispatch`, until the model
+    /// returns a final assistant message with no further tool calls, and
+    /// returns that final text. Hitting the iteration cap is an error.
+    fn call_tools(
+        &self,
+        text: &str,
+        commands: &[crate::intent::IntentCommand],
+        dispatch: &dyn Fn(&ToolCall) -> Result<String, String>,
+    ) -> Result<String, String>;
+
+    /// Streaming variant of `answer_short`: invokes `on_delta` with each
+    /// token as it arrives instead of blocking for the full completion.
+    fn answer_short_stream(&self, prompt: &str, on_delta: &mut dyn FnMut(&str)) -> Result<(), String>;
+}
+
+/// Accumulates streamed token deltas and flushes complete sentences, so a
+/// caller can fire TTS on finished sentences instead of the whole answer.
+pub struct SentenceBuffer {
+    buf: String,
+}
+
+impl SentenceBuffer {
+    pub fn new() -> Self {
+        Self { buf: String::new() }
+    }
+
+    /// Feeds a token delta in; returns any complete sentences it closed out.
+    pub fn push(&mut self, delta: &str) -> Vec<String> {
+        self.buf.push_str(delta);
+        let mut sentences = Vec::new();
+        loop {
+            let boundary = self.buf.find(['.', '?', '!', '\n']);
+            match boundary {
+                Some(idx) => {
+                    let sentence = self.buf[..=idx].trim().to_string();
+                    self.buf.drain(..=idx);
+                    if !sentence.is_empty() {
+                        sentences.push(sentence);
+                    }
+                }
+                None => break,
+            }
+        }
+        sentences
+    }
+
+    /// Returns whatever is left over once streaming has finished.
+    pub fn flush(&mut self) -> Option<String> {
+        let rest = self.buf.trim().to_string();
+        self.buf.clear();
+        if rest.is_empty() { None } else { Some(rest) }
+    }
+}
+
+impl Default for SentenceBuffer {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
-pub struct GroqClient { api_key: String }
+/// Shared OpenAI-compatible SSE streaming call, used by both Groq and
+/// Mistral since they speak the same `text/event-stream` wire format.
+fn stream_chat_completion(
+    client: &reqwest::blocking::Client,
+    url: &str,
+    api_key: &str,
+    model: &str,
+    system: &str,
+    prompt: &str,
+    on_delta: &mut dyn FnMut(&str),
+) -> Result<(), String> {
+    use std::io::{BufRead, BufReader};
+
+    let req_body = serde_json::json!({
+        "model": model,
+        "temperature": 0.2,
+        "stream": true,
+        "messages": [
+            {"role": "system", "content": system},
+            {"role": "user", "content": prompt}
+        ]
+    });
+
+    let resp = client
+        .post(url)
+        .bearer_auth(api_key)
+        .json(&req_body)
+        .send()
+        .map_err(|e| format!("http error (stream): {}", e))?;
+
+    if !resp.status().is_success() {
+        return Err(format!("stream http status: {}", resp.status()));
+    }
+
+    let reader = BufReader::new(resp);
+    for line in reader.lines() {
+        let line = line.map_err(|e| format!("stream read error: {}", e))?;
+        let Some(data) = line.strip_prefix("data: ") else { continue };
+        if data == "[DONE]" {
+            break;
+        }
+        let chunk: Value = match serde_json::from_str(data) {
+            Ok(v) => v,
+            Err(_) => continue, // ignore keep-alive/comment lines
+        };
+        if let Some(delta) = chunk["choices"][0]["delta"]["content"].as_str() {
+            if !delta.is_empty() {
+                on_delta(delta);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Builds the OpenAI-style `tools` array from the daemon's allow-listed commands.
+fn tools_schema(commands: &[crate::intent::IntentCommand]) -> Value {
+    let tools: Vec<Value> = commands
+        .iter()
+        .map(|c| {
+            serde_json::json!({
+                "type": "function",
+                "function": {
+                    "name": c.id,
+                    "description": c.description,
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "parameters": {
+                                "type": "object",
+                                "description": "Arguments for this command, if any."
+                            }
+                        },
+                        "required": []
+                    }
+                }
+            })
+        })
+        .collect();
+    Value::Array(tools)
+}
+
+/// Pulls `choices[0].message.tool_calls` out of an OpenAI-compatible response,
+/// if present. Each entry's `function.arguments` is a JSON-encoded string.
+fn parse_tool_calls(message: &Value) -> Vec<ToolCall> {
+    message
+        .get("tool_calls")
+        .and_then(|v| v.as_array())
+        .map(|calls| {
+            calls
+                .iter()
+                .filter_map(|call| {
+                    let id = call.get("id")?.as_str()?.to_string();
+                    let function = call.get("function")?;
+                    let command_id = function.get("name")?.as_str()?.to_string();
+                    let args_str = function.get("arguments").and_then(|v| v.as_str()).unwrap_or("{}");
+                    let args: Value = serde_json::from_str(args_str).unwrap_or(serde_json::json!({}));
+                    let parameters = args.get("parameters").cloned().unwrap_or(args);
+                    Some(ToolCall { id, command_id, parameters })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Runs the shared tool-calling loop against an OpenAI-compatible chat
+/// completions endpoint. `post` sends the current `messages` array (plus the
+/// tools schema) and returns the parsed JSON response.
+fn run_tool_loop(
+    text: &str,
+    dispatch: &dyn Fn(&ToolCall) -> Result<String, String>,
+    mut post: impl FnMut(&[Value]) -> Result<Value, String>,
+) -> Result<String, String> {
+    let mut messages = vec![serde_json::json!({"role": "user", "content": text})];
+
+    for _ in 0..MAX_TOOL_ITERATIONS {
+        let val = post(&messages)?;
+        let message = val["choices"][0]["message"].clone();
+        if message.is_null() {
+            return Err("tool loop: missing choices[0].message".into());
+        }
+
+        let tool_calls = parse_tool_calls(&message);
+        if tool_calls.is_empty() {
+            let content = message.get("content").and_then(|v| v.as_str()).unwrap_or("").to_string();
+            if content.trim().is_empty() {
+                return Err("tool loop: empty final answer".into());
+            }
+            return Ok(content.trim().to_string());
+        }
+
+        messages.push(message);
+        for call in &tool_calls {
+            let result = dispatch(call).unwrap_or_else(|e| format!("error: {}", e));
+            messages.push(serde_json::json!({
+                "role": "tool",
+                "tool_call_id": call.id,
+                "content": result
+            }));
+        }
+    }
 
-impl GroqClient {
-    pub fn new(api_key: String) -> Self { Self { api_key } }
+    Err("tool loop: exceeded max iterations without a final answer".into())
 }
 
-impl LlmClient for GroqClient {
+/// Returns a distinct error for providers that reject the `tools` field
+/// outright (as opposed to any other HTTP/JSON failure).
+fn tools_unsupported_error(status: reqwest::StatusCode, body: &Value) -> Option<String> {
+    if status.as_u16() == 400 {
+        let msg = body.get("error").and_then(|e| e.get("message")).and_then(|m| m.as_str()).unwrap_or("");
+        if msg.to_ascii_lowercase().contains("tool") {
+            return Some(format!("provider rejected tools field: {}", msg));
+        }
+    }
+    None
+}
+
+/// HTTP-level knobs for building a provider's client: connect/request
+/// timeouts and an optional proxy, so one query doesn't hang forever and
+/// corporate/VPN setups can route through `HTTPS_PROXY`.
+pub struct HttpClientCfg {
+    pub connect_timeout_ms: u64,
+    pub request_timeout_ms: u64,
+    pub proxy: Option<String>,
+}
+
+impl Default for HttpClientCfg {
+    fn default() -> Self {
+        Self { connect_timeout_ms: 5_000, request_timeout_ms: 20_000, proxy: None }
+    }
+}
+
+/// How many transient failures we retry within a single logical request,
+/// on top of whatever the daemon-level `OnlineTracker` is doing.
+const MAX_HTTP_RETRIES: u32 = 3;
+
+/// Sends a request, retrying on `is_connect()`/`is_timeout()` failures with
+/// the exponential-backoff schedule from `net::Backoff` instead of
+/// surfacing a raw error on the first blip.
+fn send_with_backoff(builder: &reqwest::blocking::RequestBuilder) -> Result<reqwest::blocking::Response, String> {
+    let mut backoff = crate::net::Backoff::new();
+    for attempt in 0..=MAX_HTTP_RETRIES {
+        // Our request bodies are always buffered JSON, so this is always Some.
+        let attempt_builder = builder.try_clone().ok_or("request body not cloneable for retry")?;
+        match attempt_builder.send() {
+            Ok(resp) => return Ok(resp),
+            Err(e) if attempt < MAX_HTTP_RETRIES && (e.is_connect() || e.is_timeout()) => {
+                let delay = backoff.next_delay();
+                eprintln!("llm: transient error ({}), retrying in {:?}", e, delay);
+                std::thread::sleep(delay);
+            }
+            Err(e) => {
+                return Err(format!(
+                    "http error: connect={} timeout={} source={}",
+                    e.is_connect(),
+                    e.is_timeout(),
+                    e
+                ))
+            }
+        }
+    }
+    Err("http error: exhausted retries".into())
+}
+
+fn build_http_client(cfg: &HttpClientCfg) -> Result<reqwest::blocking::Client, String> {
+    let mut builder = reqwest::blocking::Client::builder()
+        .connect_timeout(std::time::Duration::from_millis(cfg.connect_timeout_ms))
+        .timeout(std::time::Duration::from_millis(cfg.request_timeout_ms));
+
+    let proxy_url = cfg.proxy.clone().or_else(|| std::env::var("HTTPS_PROXY").ok());
+    if let Some(proxy_url) = proxy_url {
+        let proxy = reqwest::Proxy::https(&proxy_url).map_err(|e| format!("invalid proxy url: {}", e))?;
+        builder = builder.proxy(proxy);
+    }
+
+    builder.build().map_err(|e| format!("http client build: {}", e))
+}
+
+/// A single `LlmClient` implementation for any OpenAI-spec chat completions
+/// endpoint (Groq, Mistral, Together, OpenRouter, local Ollama, ...),
+/// parameterized by base URL and model names instead of being copy-pasted
+/// per provider. Construct one via [`build_client`], which resolves presets
+/// and config overrides into the values this struct needs.
+pub struct OpenAiCompatibleClient {
+    base_url: String,
+    chat_model: String,
+    tts_model: Option<String>,
+    embedding_model: Option<String>,
+    api_key: String,
+    http: reqwest::blocking::Client,
+}
+
+impl OpenAiCompatibleClient {
+    pub fn new(
+        api_key: String,
+        base_url: String,
+        chat_model: String,
+        tts_model: Option<String>,
+        embedding_model: Option<String>,
+        http_cfg: HttpClientCfg,
+    ) -> Result<Self, String> {
+        let http = build_http_client(&http_cfg)?;
+        Ok(Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            chat_model,
+            tts_model,
+            embedding_model,
+            api_key,
+            http,
+        })
+    }
+
+    fn chat_url(&self) -> String {
+        format!("{}/chat/completions", self.base_url)
+    }
+
+    fn speech_url(&self) -> String {
+        format!("{}/audio/speech", self.base_url)
+    }
+
+    fn embeddings_url(&self) -> String {
+        format!("{}/embeddings", self.base_url)
+    }
+}
+
+impl LlmClient for OpenAiCompatibleClient {
     fn classify_intent(&self, text: &str, commands: &[crate::intent::IntentCommand]) -> Result<LlmIntent, String> {
-        let url = "https://api.groq.com/openai/v1/chat/completions";
         let commands_list: Vec<_> = commands.iter().map(|c| serde_json::json!({"id": c.id, "description": c.description})).collect();
         let system = "You are an intent classifier. Return ONLY a JSON object with keys: command_id, parameters, confidence. Choose the best matching command_id from the provided list or null if none.";
         let user_prompt = serde_json::json!({"text": text, "commands": commands_list}).to_string();
-        let client = reqwest::blocking::Client::new();
         let req_body = serde_json::json!({
-            "model": "llama-3.1-8b-instant",
+            "model": self.chat_model,
             "temperature": 0.0,
             "response_format": {"type": "json_object"},
             "messages": [
@@ -35,10 +369,10 @@ impl LlmClient for GroqClient {
                 {"role": "user", "content": user_prompt}
             ]
         });
-        let resp = client.post(url)
+        let builder = self.http.post(self.chat_url())
             .bearer_auth(&self.api_key)
-            .json(&req_body)
-            .send().map_err(|e| format!("http error: {}", e))?;
+            .json(&req_body);
+        let resp = send_with_backoff(&builder)?;
         let val: Value = resp.json().map_err(|e| format!("json error: {}", e))?;
         let content = val["choices"][0]["message"]["content"].as_str().unwrap_or("{}");
         let parsed: Value = serde_json::from_str(content).unwrap_or(serde_json::json!({"command_id": null, "parameters": {}, "confidence": 0.0}));
@@ -50,54 +384,85 @@ impl LlmClient for GroqClient {
     }
 
     fn summarize_search(&self, _query: &str, snippets: &[String]) -> Result<String, String> {
-        let api_key = &self.api_key;
-        let url = "https://api.groq.com/openai/v1/chat/completions";
         let text = if let Some(s) = snippets.first() { s } else { return Err("no snippets".into()) };
         let system = "Summarize the following answer into a few concise sentences suitable for speech. Return only the sentences.";
-        let user_prompt = text;
         let req_body = serde_json::json!({
-            "model": "llama-3.1-8b-instant",
+            "model": self.chat_model,
             "temperature": 0.0,
             "messages": [
                 {"role": "system", "content": system},
-                {"role": "user", "content": user_prompt}
+                {"role": "user", "content": text}
             ]
         });
-        let client = reqwest::blocking::Client::new();
-        let resp = client.post(url)
-            .bearer_auth(api_key)
-            .json(&req_body)
-            .send().map_err(|e| format!("http error: {}", e))?;
+        let builder = self.http.post(self.chat_url())
+            .bearer_auth(&self.api_key)
+            .json(&req_body);
+        let resp = send_with_backoff(&builder)?;
         let val: Value = resp.json().map_err(|e| e.to_string())?;
         let content = val["choices"][0]["message"]["content"].as_str().unwrap_or("").to_string();
         if content.is_empty() { Err("empty summary".into()) } else { Ok(content) }
     }
 
     fn answer_short(&self, prompt: &str) -> Result<String, String> {
-        let api_key = &self.api_key;
-        let url = "https://api.groq.com/openai/v1/chat/completions";
         let system = "You are a helpful voice assistant named Bumblebee. Answer the user's question concisely in one or two sentences. Avoid markdown; output plain text only.";
         let req_body = serde_json::json!({
-            "model": "llama-3.1-8b-instant",
+            "model": self.chat_model,
             "temperature": 0.2,
             "messages": [
                 {"role": "system", "content": system},
                 {"role": "user", "content": prompt}
             ]
         });
-        let client = reqwest::blocking::Client::new();
-        let resp = client.post(url)
-            .bearer_auth(api_key)
-            .json(&req_body)
-            .send().map_err(|e| format!("http error: {}", e))?;
+        let builder = self.http.post(self.chat_url())
+            .bearer_auth(&self.api_key)
+            .json(&req_body);
+        let resp = send_with_backoff(&builder)?;
         let val: Value = resp.json().map_err(|e| e.to_string())?;
         let content = val["choices"][0]["message"]["content"].as_str().unwrap_or("").to_string();
         if content.trim().is_empty() { Err("empty answer".into()) } else { Ok(content.trim().to_string()) }
     }
 
+    fn answer_short_stream(&self, prompt: &str, on_delta: &mut dyn FnMut(&str)) -> Result<(), String> {
+        let system = "You are a helpful voice assistant named Bumblebee. Answer the user's question concisely in one or two sentences. Avoid markdown; output plain text only.";
+        stream_chat_completion(&self.http, &self.chat_url(), &self.api_key, &self.chat_model, system, prompt, on_delta)
+    }
+
+    fn call_tools(
+        &self,
+        text: &str,
+        commands: &[crate::intent::IntentCommand],
+        dispatch: &dyn Fn(&ToolCall) -> Result<String, String>,
+    ) -> Result<String, String> {
+        let tools = tools_schema(commands);
+        let url = self.chat_url();
+        run_tool_loop(text, dispatch, |messages| {
+            let req_body = serde_json::json!({
+                "model": self.chat_model,
+                "temperature": 0.0,
+                "tools": tools,
+                "tool_choice": "auto",
+                "messages": messages,
+            });
+            let builder = self.http.post(&url)
+                .bearer_auth(&self.api_key)
+                .json(&req_body);
+            let resp = send_with_backoff(&builder)?;
+            let status = resp.status();
+            let val: Value = resp.json().map_err(|e| format!("json error: {}", e))?;
+            if let Some(err) = tools_unsupported_error(status, &val) {
+                return Err(err);
+            }
+            if !status.is_success() {
+                return Err(format!("tool-call status: {} body={}", status, val));
+            }
+            Ok(val)
+        })
+    }
+
     fn tts(&self, text: &str) -> Result<Vec<u8>, String> {
-        let url = "https://api.groq.com/openai/v1/audio/speech";
-        let model = std::env::var("BTWD_TTS_MODEL").unwrap_or_else(|_| "canopylabs/orpheus-v1-english".to_string());
+        let Some(model) = &self.tts_model else {
+            return Err(format!("provider at {} has no configured tts model", self.base_url));
+        };
         let voice = std::env::var("BTWD_TTS_VOICE").unwrap_or_else(|_| "alloy".to_string());
         let response_format = std::env::var("BTWD_TTS_FORMAT").unwrap_or_else(|_| "wav".to_string());
         let req_body = serde_json::json!({
@@ -107,123 +472,125 @@ impl LlmClient for GroqClient {
             "response_format": response_format,
             "speed": 1.0,
         });
-        let client = reqwest::blocking::Client::new();
-        let resp = client.post(url)
+        let builder = self.http.post(self.speech_url())
             .bearer_auth(&self.api_key)
-            .json(&req_body)
-            .send().map_err(|e| format!("http error: {}", e))?;
+            .json(&req_body);
+        let resp = send_with_backoff(&builder)?;
         if !resp.status().is_success() { return Err(format!("tts http status: {}", resp.status())); }
         let bytes = resp.bytes().map_err(|e| format!("read body: {}", e))?.to_vec();
         Ok(bytes)
     }
-}
 
-pub struct MistralClient { api_key: String }
-
-impl MistralClient { pub fn new(api_key: String) -> Self { Self { api_key } } }
-
-impl LlmClient for MistralClient {
-    fn classify_intent(&self, text: &str, commands: &[crate::intent::IntentCommand]) -> Result<LlmIntent, String> {
-        let url = "https://api.mistral.ai/v1/chat/completions";
-        let commands_list: Vec<_> = commands.iter().map(|c| serde_json::json!({"id": c.id, "description": c.description})).collect();
-        let system = "You are an intent classifier. Return ONLY a JSON object with keys: command_id, parameters, confidence. Choose the best matching command_id from the provided list or null if none.";
-        let user_prompt = serde_json::json!({"text": text, "commands": commands_list}).to_string();
-        let client = reqwest::blocking::Client::new();
+    fn embed(&self, text: &str) -> Result<Vec<f32>, String> {
+        let Some(model) = &self.embedding_model else {
+            return Err(format!("provider at {} has no configured embedding model", self.base_url));
+        };
         let req_body = serde_json::json!({
-            "model": "mistral-small-latest",
-            "temperature": 0.0,
-            "response_format": {"type": "json_object"},
-            "messages": [
-                {"role": "system", "content": system},
-                {"role": "user", "content": user_prompt}
-            ]
+            "model": model,
+            "input": text,
         });
-        let resp = client
-            .post(url)
+        let builder = self.http.post(self.embeddings_url())
             .bearer_auth(&self.api_key)
-            .json(&req_body)
-            .send()
-            .map_err(|e| {
-                format!(
-                    "http error (mistral classify): connect={} timeout={} source={}",
-                    e.is_connect(),
-                    e.is_timeout(),
-                    e
-                )
-            })?;
+            .json(&req_body);
+        let resp = send_with_backoff(&builder)?;
+        if !resp.status().is_success() { return Err(format!("embeddings http status: {}", resp.status())); }
         let val: Value = resp.json().map_err(|e| format!("json error: {}", e))?;
-        let content = val["choices"][0]["message"]["content"].as_str().unwrap_or("{}");
-        let parsed: Value = serde_json::from_str(content).unwrap_or(serde_json::json!({"command_id": null, "parameters": {}, "confidence": 0.0}));
-        Ok(LlmIntent {
-            command_id: parsed["command_id"].as_str().map(|s| s.to_string()),
-            parameters: parsed.get("parameters").cloned().unwrap_or(serde_json::json!({})),
-            confidence: parsed["confidence"].as_f64().unwrap_or(0.0) as f32,
-        })
+        let embedding = val["data"][0]["embedding"]
+            .as_array()
+            .ok_or("embeddings response missing data[0].embedding")?
+            .iter()
+            .filter_map(|v| v.as_f64().map(|f| f as f32))
+            .collect::<Vec<f32>>();
+        if embedding.is_empty() { Err("empty embedding".into()) } else { Ok(embedding) }
     }
+}
 
-    fn summarize_search(&self, _query: &str, snippets: &[String]) -> Result<String, String> {
-        let url = "https://api.mistral.ai/v1/chat/completions";
-        let text = if let Some(s) = snippets.first() { s } else { return Err("no snippets".into()) };
-        let system = "Summarize the following answer into a few concise sentences suitable for speech. Return only the sentences.";
-        let user_prompt = text;
-        let req_body = serde_json::json!({
-            "model": "mistral-small-latest",
-            "temperature": 0.0,
-            "messages": [
-                {"role": "system", "content": system},
-                {"role": "user", "content": user_prompt}
-            ]
-        });
-        let client = reqwest::blocking::Client::new();
-        let resp = client
-            .post(url)
-            .bearer_auth(&self.api_key)
-            .json(&req_body)
-            .send()
-            .map_err(|e| {
-                format!(
-                    "http error (mistral summarize): connect={} timeout={} source={}",
-                    e.is_connect(),
-                    e.is_timeout(),
-                    e
-                )
-            })?;
-        let val: Value = resp.json().map_err(|e| e.to_string())?;
-        let content = val["choices"][0]["message"]["content"].as_str().unwrap_or("").to_string();
-        if content.is_empty() { Err("empty summary".into()) } else { Ok(content) }
-    }
+/// A known provider's defaults, analogous to aichat's `register_client!`
+/// table: enough to build a working client from just a `"type"` tag in
+/// config, with `LlmCfg` fields available to override any of it.
+struct ProviderPreset {
+    base_url: &'static str,
+    chat_model: &'static str,
+    tts_model: Option<&'static str>,
+    embedding_model: Option<&'static str>,
+    api_key_env: &'static str,
+}
 
-    fn answer_short(&self, prompt: &str) -> Result<String, String> {
-        let url = "https://api.mistral.ai/v1/chat/completions";
-        let system = "You are a helpful voice assistant named Bumblebee. Answer the user's question concisely in one or two sentences. Avoid markdown; output plain text only.";
-        let req_body = serde_json::json!({
-            "model": "mistral-small-latest",
-            "temperature": 0.2,
-            "messages": [
-                {"role": "system", "content": system},
-                {"role": "user", "content": prompt}
-            ]
-        });
-        let client = reqwest::blocking::Client::new();
-        let resp = client
-            .post(url)
-            .bearer_auth(&self.api_key)
-            .json(&req_body)
-            .send()
-            .map_err(|e| {
-                format!(
-                    "http error (mistral answer): connect={} timeout={} source={}",
-                    e.is_connect(),
-                    e.is_timeout(),
-                    e
-                )
-            })?;
-        let val: Value = resp.json().map_err(|e| e.to_string())?;
-        let content = val["choices"][0]["message"]["content"].as_str().unwrap_or("").to_string();
-        if content.trim().is_empty() { Err("empty answer".into()) } else { Ok(content.trim().to_string()) }
+fn provider_preset(provider_type: &str) -> Option<ProviderPreset> {
+    match provider_type {
+        "groq" => Some(ProviderPreset {
+            base_url: "https://api.groq.com/openai/v1",
+            chat_model: "llama-3.1-8b-instant",
+            tts_model: Some("canopylabs/orpheus-v1-english"),
+            embedding_model: None,
+            api_key_env: "GROQ_API_KEY",
+        }),
+        "mistral" => Some(ProviderPreset {
+            base_url: "https://api.mistral.ai/v1",
+            chat_model: "mistral-small-latest",
+            tts_model: None,
+            embedding_model: Some("mistral-embed"),
+            api_key_env: "MISTRAL_API_KEY",
+        }),
+        "together" => Some(ProviderPreset {
+            base_url: "https://api.together.xyz/v1",
+            chat_model: "meta-llama/Llama-3.3-70B-Instruct-Turbo",
+            tts_model: None,
+            embedding_model: Some("togethercomputer/m2-bert-80M-8k-retrieval"),
+            api_key_env: "TOGETHER_API_KEY",
+        }),
+        "openrouter" => Some(ProviderPreset {
+            base_url: "https://openrouter.ai/api/v1",
+            chat_model: "meta-llama/llama-3.1-8b-instruct",
+            tts_model: None,
+            embedding_model: None,
+            api_key_env: "OPENROUTER_API_KEY",
+        }),
+        "ollama" => Some(ProviderPreset {
+            base_url: "http://localhost:11434/v1",
+            chat_model: "llama3.1",
+            tts_model: None,
+            embedding_model: Some("nomic-embed-text"),
+            api_key_env: "OLLAMA_API_KEY",
+        }),
+        _ => None,
     }
+}
 
-    fn tts(&self, _text: &str) -> Result<Vec<u8>, String> {
-        Err("Mistral TTS not supported".into())
-    }
+/// Builds the configured `LlmClient` from `cfg.llm`. `provider` is the
+/// `"type"` tag: a known preset name (`groq`, `mistral`, `together`,
+/// `openrouter`, `ollama`) or any other string, in which case `base_url`
+/// and `model` must be supplied in config for a fully custom OpenAI-spec
+/// endpoint (e.g. a self-hosted one).
+pub fn build_client(cfg: &crate::config::LlmCfg) -> Result<std::sync::Arc<dyn LlmClient>, String> {
+    let preset = provider_preset(&cfg.provider);
+
+    let base_url = cfg.base_url.clone()
+        .or_else(|| preset.as_ref().map(|p| p.base_url.to_string()))
+        .ok_or_else(|| format!("unknown llm provider '{}': set llm.base_url in config", cfg.provider))?;
+
+    let chat_model = cfg.model.clone()
+        .or_else(|| preset.as_ref().map(|p| p.chat_model.to_string()))
+        .ok_or_else(|| format!("unknown llm provider '{}': set llm.model in config", cfg.provider))?;
+
+    let tts_model = cfg.tts_model.clone()
+        .or_else(|| preset.as_ref().and_then(|p| p.tts_model.map(|s| s.to_string())));
+
+    let embedding_model = cfg.embedding_model.clone()
+        .or_else(|| preset.as_ref().and_then(|p| p.embedding_model.map(|s| s.to_string())));
+
+    let api_key_env = cfg.api_key_env.clone()
+        .or_else(|| preset.as_ref().map(|p| p.api_key_env.to_string()))
+        .unwrap_or_else(|| "LLM_API_KEY".to_string());
+
+    let api_key = std::env::var(&api_key_env).map_err(|e| format!("missing {}: {}", api_key_env, e))?;
+
+    let http_cfg = HttpClientCfg {
+        connect_timeout_ms: cfg.connect_timeout_ms.unwrap_or(5_000),
+        request_timeout_ms: cfg.request_timeout_ms.unwrap_or(20_000),
+        proxy: cfg.proxy.clone(),
+    };
+
+    let client = OpenAiCompatibleClient::new(api_key, base_url, chat_model, tts_model, embedding_model, http_cfg)?;
+    Ok(std::sync::Arc::new(client))
 }
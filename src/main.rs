@@ -10,19 +10,45 @@ mod ml;
 mod ui;
 mod tts;
 mod search;
+mod memory;
+mod http;
+mod i18n;
 mod net;
 mod executor;
 mod llm;
 mod decision;
 mod manager;
+mod remote;
+mod telegram;
 
 use error::{BtwError, Result};
 use std::{fs, time::Instant};
 use xdg::BaseDirectories;
-use std::sync::mpsc::Receiver;
+use crossbeam_channel::{select, Receiver, Sender};
 use std::sync::Arc;
 use std::time::Duration;
 use std::path::PathBuf;
+use base64::Engine;
+
+/// The unified set of things the main loop reacts to, multiplexed with a
+/// single `crossbeam_channel::select!` instead of blocking on audio alone
+/// and polling everything else frame-by-frame.
+enum Event {
+    Audio(Vec<i16>),
+    Tick,
+    Confirm { id: String, yes: bool },
+    RemoteTranscript(String),
+    Shutdown,
+}
+
+/// Where a transcript handed to `handle_transcript` came from. Remote-origin
+/// transcripts are additionally gated against `cfg.remote.allow_list` so a
+/// remote peer can never trigger a command the local policy forbids.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TranscriptOrigin {
+    Local,
+    Remote,
+}
 
 fn normalize_short(s: &str) -> String {
     let mut out = String::with_capacity(s.len());
@@ -43,6 +69,10 @@ fn handle_transcript(
     exec: &mut executor::Executor,
     intent_router: &intent::IntentRouter,
     llm_client: &Arc<dyn llm::LlmClient>,
+    online: &Arc<net::OnlineTracker>,
+    deferred_question: &mut Option<String>,
+    origin: TranscriptOrigin,
+    remote_answers: Option<&tokio::sync::broadcast::Sender<String>>,
 ) {
     let norm = normalize_short(text);
 
@@ -70,6 +100,22 @@ fn handle_transcript(
     let passed_threshold = det_score >= cfg.intent.deterministic_threshold;
 
     if is_valid_allowlisted && passed_threshold {
+        // Remote callers get an additional allow-list check, intersected
+        // with the local commands.json-driven routing above: a command a
+        // remote peer isn't explicitly permitted to run never reaches
+        // `exec`, regardless of what local policy would otherwise allow.
+        // This is one allow-list shared by every authenticated connection,
+        // not a per-peer one — `cfg.remote` has a single shared secret, so
+        // every remote caller is the same trust class (see `remote`'s
+        // module doc for what a real per-peer allow-list would need).
+        if origin == TranscriptOrigin::Remote {
+            let command_id = routed.command_id.as_deref().unwrap_or("");
+            if !cfg.remote.allow_list.iter().any(|id| id == command_id) {
+                eprintln!("remote: command '{}' not in remote.allow_list, ignoring", command_id);
+                return;
+            }
+        }
+
         if routed.dangerous {
             let status = exec.handle_intent(&intent::IntentResult {
                 requires_confirmation: true,
@@ -85,6 +131,56 @@ fn handle_transcript(
         return;
     }
 
+    // 2b) Multi-step agent fallback. The deterministic router above only
+    // ever picks one `IntentCommand`, so a request that needs several
+    // ("what's the weather in London and Paris") falls through it even
+    // though `det_score` shows it's command-shaped rather than a plain
+    // question. Hand those to `LlmClient::call_tools`'s tool-calling loop,
+    // which can chain as many commands as the model asks for; each one
+    // still goes through `exec.handle_intent`, so throttling, dangerous
+    // confirmation and the remote allow-list all apply exactly as they do
+    // to a single deterministically-routed command.
+    if is_valid_allowlisted && det_score >= cfg.intent.llm_fallback_threshold {
+        let commands = intent_router.commands();
+        let exec_cell = std::cell::RefCell::new(&mut *exec);
+        let dispatch = |call: &llm::ToolCall| -> std::result::Result<String, String> {
+            if origin == TranscriptOrigin::Remote && !cfg.remote.allow_list.iter().any(|id| id == &call.command_id) {
+                return Err(format!("command '{}' not in remote.allow_list", call.command_id));
+            }
+            let Some(spec) = commands.iter().find(|c| c.id == call.command_id) else {
+                return Err(format!("unknown command_id '{}'", call.command_id));
+            };
+            let status = exec_cell.borrow_mut().handle_intent(&intent::IntentResult {
+                command_id: Some(spec.id.clone()),
+                parameters: call.parameters.clone(),
+                dangerous: spec.dangerous,
+                requires_confirmation: spec.dangerous,
+                deterministic_score: None,
+            });
+            Ok(format!("{:?}", status))
+        };
+
+        match llm_client.call_tools(text, commands, &dispatch) {
+            Ok(answer) => {
+                eprintln!("assistant: agent -> {}", answer);
+                if origin == TranscriptOrigin::Remote {
+                    if let Some(tx) = remote_answers {
+                        let _ = tx.send(answer);
+                    }
+                } else {
+                    ui::notify_text(cfg.ui.osd, cfg.ui.osd_timeout_ms, "Btw", &answer);
+                    if cfg.speech_output.enabled {
+                        tts::speak_async(answer, cfg.speech_output.clone(), Some(i18n::active().code().to_string()));
+                    }
+                }
+                return;
+            }
+            Err(e) => {
+                eprintln!("assistant: call_tools agent failed ({}), falling back to single-shot routing", e);
+            }
+        }
+    }
+
     // 3) Non-command -> Question routing.
     // If below threshold, treat as question (never command).
     let question = text.trim();
@@ -92,40 +188,161 @@ fn handle_transcript(
         return;
     }
 
+    // If we currently believe we're offline, don't hammer the LLM/search
+    // path: defer this question and pick it back up once the connectivity
+    // probe in `run()` sees us come back online.
+    if matches!(online.state(), net::IsOnline::Offline) {
+        eprintln!("assistant: offline, deferring question");
+        *deferred_question = Some(question.to_string());
+        ui::notify_text(cfg.ui.osd, cfg.ui.osd_timeout_ms, "Btw", "No internet — I'll answer once I reconnect.");
+        return;
+    }
+
     // Strict workflow: ask LLM first with a knowledge-check. Only if it explicitly
     // returns the sentinel string do we call Tavily and then re-ask.
     // No UI notifications are shown until the final answer is ready.
     if cfg.search.enabled {
         eprintln!("assistant: question; strict LLM→Tavily gating");
+        // Remote-origin questions must never pop an OSD notification or
+        // speak on the host: the peer that asked gets the answer back over
+        // its own socket via `RemoteAnswerSink` instead.
+        let sink: Arc<dyn search::AnswerSink> = if origin == TranscriptOrigin::Remote {
+            let Some(tx) = remote_answers else {
+                eprintln!("assistant: remote question with no answer channel, dropping");
+                return;
+            };
+            Arc::new(remote::RemoteAnswerSink { answer_tx: tx.clone() })
+        } else {
+            Arc::new(search::DesktopSink {
+                ui_enabled: cfg.ui.osd,
+                ui_timeout_ms: cfg.ui.osd_timeout_ms,
+                tts: cfg.speech_output.clone(),
+            })
+        };
         search::search_and_summarize_async(
             question.to_string(),
             cfg.search.clone(),
-            cfg.ui.osd,
-            cfg.ui.osd_timeout_ms,
-            cfg.speech_output.clone(),
             llm_client.clone(),
+            sink,
         );
         return;
     }
 
-    // If search is disabled, fall back to direct LLM answer.
+    // If search is disabled, fall back to direct LLM answer. Stream tokens
+    // so we can hand complete sentences to TTS as they arrive instead of
+    // waiting for the whole answer. Remote-origin questions never speak or
+    // notify on the host — streamed sentences are only buffered into `full`
+    // and the finished answer goes back over the socket at the end.
     eprintln!("assistant: question; asking LLM (search disabled)");
-    let ans = llm_client.answer_short(question).unwrap_or_else(|e| {
-        eprintln!("assistant: LLM answer error: {}", e);
-        "I don’t know.".to_string()
+    let speak_on_host = origin == TranscriptOrigin::Local;
+    let mut full = String::new();
+    let mut sentence_buf = llm::SentenceBuffer::new();
+    let speech_cfg = cfg.speech_output.clone();
+    let stream_res = llm_client.answer_short_stream(question, &mut |delta: &str| {
+        full.push_str(delta);
+        for sentence in sentence_buf.push(delta) {
+            if speak_on_host && speech_cfg.enabled {
+                tts::speak_async(sentence, speech_cfg.clone(), Some(i18n::active().code().to_string()));
+            }
+        }
     });
-    ui::notify_text(cfg.ui.osd, cfg.ui.osd_timeout_ms, "Btw", &ans);
-    if cfg.speech_output.enabled {
-        tts::speak_async(ans, cfg.speech_output.clone());
+
+    if let Err(e) = stream_res {
+        eprintln!("assistant: LLM stream error: {}", e);
+        full = "I don’t know.".to_string();
+    } else if let Some(rest) = sentence_buf.flush() {
+        if speak_on_host && cfg.speech_output.enabled {
+            tts::speak_async(rest, cfg.speech_output.clone(), Some(i18n::active().code().to_string()));
+        }
+    }
+
+    if speak_on_host {
+        ui::notify_text(cfg.ui.osd, cfg.ui.osd_timeout_ms, "Btw", &full);
+    } else if let Some(tx) = remote_answers {
+        let _ = tx.send(full);
     }
 }
 fn main() {
-    if let Err(e) = run() {
+    let mut args = std::env::args().skip(1);
+    let result = match args.next().as_deref() {
+        Some("ingest") => run_ingest(&args.collect::<Vec<_>>()),
+        Some("list-devices") => run_list_devices(),
+        _ => run(),
+    };
+    if let Err(e) = result {
         eprintln!("btwd startup error: {}", e);
         std::process::exit(1);
     }
 }
 
+/// `btwd ingest <file>...` CLI entry point. This is the only thing in the
+/// daemon that ever calls `memory::MemoryStore::ingest` — without it
+/// `cfg.search.memory_path` always points at an empty store and
+/// `search::answer_with_memory` falls through to Tavily on every run, no
+/// matter how the config is tuned. Loads just enough config to build an
+/// `LlmClient` and find the memory store path, then chunks+embeds each
+/// file and appends it.
+fn run_ingest(paths: &[String]) -> Result<()> {
+    if paths.is_empty() {
+        eprintln!("usage: btwd ingest <file> [file...]");
+        return Ok(());
+    }
+
+    let xdg = BaseDirectories::with_prefix("btw")
+        .map_err(|e| BtwError::XdgError { message: e.to_string() })?;
+    let config_path = xdg.find_config_file("config.toml")
+        .ok_or_else(|| expected_missing(&xdg, "config.toml", "config"))?;
+    let env_path = xdg.find_config_file(".env")
+        .ok_or_else(|| expected_missing(&xdg, ".env", "env"))?;
+
+    dotenvy::from_path(&env_path)
+        .map_err(|e| BtwError::EnvLoadError { path: env_path.clone(), source: e })?;
+
+    let cfg_str = fs::read_to_string(&config_path)
+        .map_err(|e| BtwError::ReadError { path: config_path.clone(), source: e })?;
+    let cfg = config::Config::from_toml_str(&cfg_str)
+        .map_err(|msg| BtwError::ParseError { path: config_path.clone(), kind: "toml", message: msg })?;
+
+    let memory_path = cfg.search.memory_path.clone().ok_or_else(|| BtwError::ParseError {
+        path: config_path.clone(),
+        kind: "toml",
+        message: "search.memory_path must be set to ingest into local memory".to_string(),
+    })?;
+
+    let llm_client: Arc<dyn llm::LlmClient> = llm::build_client(&cfg.llm)
+        .map_err(|msg| BtwError::ParseError { path: config_path.clone(), kind: "llm", message: msg })?;
+
+    let mut store = memory::MemoryStore::load(&memory_path);
+    for path in paths {
+        let text = fs::read_to_string(path)
+            .map_err(|e| BtwError::ReadError { path: PathBuf::from(path), source: e })?;
+        let added = store.ingest(&text, &llm_client).map_err(|message| BtwError::ParseError {
+            path: PathBuf::from(path),
+            kind: "ingest",
+            message,
+        })?;
+        eprintln!("ingest: {} -> {} chunk(s) added to {}", path, added, memory_path.display());
+    }
+
+    Ok(())
+}
+
+/// `btwd list-devices` CLI entry point: prints every input device
+/// `audio::list_input_devices` sees, so a user can pick the right
+/// `audio.input_device` value for `config.toml` without guessing at the
+/// raw ALSA/CoreAudio name.
+fn run_list_devices() -> Result<()> {
+    let names = audio::list_input_devices()?;
+    if names.is_empty() {
+        eprintln!("no input devices found");
+        return Ok(());
+    }
+    for name in names {
+        println!("{}", name);
+    }
+    Ok(())
+}
+
 fn run() -> Result<()> {
     let xdg = BaseDirectories::with_prefix("btw")
         .map_err(|e| BtwError::XdgError { message: e.to_string() })?;
@@ -145,6 +362,8 @@ fn run() -> Result<()> {
     let cfg = config::Config::from_toml_str(&cfg_str)
         .map_err(|msg| BtwError::ParseError { path: config_path.clone(), kind: "toml", message: msg })?;
 
+    i18n::init(&cfg.locale);
+
     let commands_str = fs::read_to_string(&commands_path)
         .map_err(|e| BtwError::ReadError { path: commands_path.clone(), source: e })?;
     let _commands = commands::parse_commands_json(&commands_str)
@@ -169,8 +388,8 @@ fn run() -> Result<()> {
     eprintln!("Porcupine device: {}", porcupine.device());
 
     // ---- Audio thread
-    let (_audio_handle, rx): (std::thread::JoinHandle<()>, Receiver<Vec<i16>>) =
-        audio::start_listening(&porcupine)?;
+    let (_audio_handle, audio_rx): (std::thread::JoinHandle<()>, Receiver<Vec<i16>>) =
+        audio::start_listening(&porcupine, &cfg.audio)?;
 
     eprintln!("Listening for wake word...");
 
@@ -181,35 +400,9 @@ fn run() -> Result<()> {
     let frame_length = porcupine.frame_length();
     let frame_ms = (frame_length as f64) * 1000.0 / sample_rate as f64;
 
-    let llm_client: Arc<dyn llm::LlmClient> = match cfg.llm.provider.as_str() {
-        "groq" => {
-            std::env::var("GROQ_API_KEY").map_err(|e| {
-                BtwError::ParseError {
-                    path: config_path.clone(),
-                    kind: "env",
-                    message: format!("missing GROQ_API_KEY: {}", e),
-                }
-            })?;
-            Arc::new(llm::GroqClient::new(std::env::var("GROQ_API_KEY").unwrap()))
-        }
-        "mistral" => {
-            std::env::var("MISTRAL_API_KEY").map_err(|e| {
-                BtwError::ParseError {
-                    path: config_path.clone(),
-                    kind: "env",
-                    message: format!("missing MISTRAL_API_KEY: {}", e),
-                }
-            })?;
-            Arc::new(llm::MistralClient::new(std::env::var("MISTRAL_API_KEY").unwrap()))
-        }
-        p => {
-            return Err(BtwError::ParseError {
-                path: config_path.clone(),
-                kind: "llm",
-                message: format!("unknown provider '{}'", p),
-            })
-        }
-    };
+    let llm_client: Arc<dyn llm::LlmClient> = llm::build_client(&cfg.llm).map_err(|msg| {
+        BtwError::ParseError { path: config_path.clone(), kind: "llm", message: msg }
+    })?;
 
     let intent_router = intent::IntentRouter::from_file(
         &commands_path,
@@ -229,6 +422,8 @@ fn run() -> Result<()> {
         executor::ExecutionCfg {
             confirmation_timeout_seconds: cfg.execution.confirmation_timeout_seconds,
             dry_run: cfg.execution.dry_run,
+            action_throttle_ms: cfg.execution.action_throttle_ms,
+            on_busy: cfg.execution.on_busy,
         },
     )?;
 
@@ -249,12 +444,69 @@ fn run() -> Result<()> {
     let mut silence_ms = 0.0;
     let mut start_time: Option<Instant> = None;
     let mut saw_post_wake_speech = false;
+    // Set on wake-word detection, cleared once the transcript for that
+    // utterance comes back; lets synthetic-source integration tests assert
+    // on wake-to-transcript latency instead of just the final transcript.
+    let mut wake_detected_at: Option<Instant> = None;
 
     let mut porcupine = porcupine;
     let mut last_heartbeat = Instant::now();
     let mut last_listening_debug = Instant::now();
     let mut pending_confirm_request_id: Option<String> = None;
 
+    // Tracks whether we currently believe we're online, so LLM/search calls
+    // can be deferred rather than failing hard during a connectivity blip.
+    let online = Arc::new(net::OnlineTracker::new());
+    let mut next_probe_at = Instant::now();
+    let mut deferred_question: Option<String> = None;
+
+    // Event sources, multiplexed below with a single `select!` instead of
+    // blocking on audio alone and polling everything else per-frame. `tick_rx`
+    // drives both the confirmation-timeout check and the connectivity probe
+    // at a fixed cadence, independent of whether audio is arriving.
+    let tick_rx = crossbeam_channel::tick(Duration::from_millis(200));
+
+    // Confirm events are pushed here directly by the click callback that
+    // the `Event::Tick` arm below hands to `ui::notify_confirm_actions`.
+    let (confirm_tx, confirm_rx): (Sender<(String, bool)>, Receiver<(String, bool)>) =
+        crossbeam_channel::unbounded();
+
+    let (shutdown_tx, shutdown_rx): (Sender<()>, Receiver<()>) = crossbeam_channel::bounded(1);
+    if let Err(e) = ctrlc::set_handler(move || {
+        let _ = shutdown_tx.send(());
+    }) {
+        eprintln!("warning: failed to install signal handler: {}", e);
+    }
+
+    // Remote control/audio-bridge: turns the daemon into a headless voice
+    // endpoint controllable from another machine. Its audio frames are
+    // merged below as just another `Event::Audio` source, and its
+    // transcripts as `Event::RemoteTranscript`, so the rest of the pipeline
+    // (wake-word state machine aside) doesn't need to know a frame or
+    // sentence came over the network rather than the local microphone.
+    let remote = remote::spawn(cfg.remote.clone(), sample_rate, frame_length)?;
+
+    // Telegram frontend: same question pipeline as above, but answers go
+    // back as chat replies instead of desktop notifications/TTS. Disabled
+    // by default via `cfg.telegram.enabled`.
+    telegram::spawn(cfg.telegram.clone(), cfg.search.clone(), llm_client.clone());
+    // `never()` channels so the `select!` below can treat "remote disabled"
+    // as just another source that happens to never fire, instead of
+    // conditionally building the macro call.
+    let remote_audio_rx: Receiver<Vec<i16>> = remote
+        .as_ref()
+        .map(|r| r.audio_rx.clone())
+        .unwrap_or_else(crossbeam_channel::never);
+    let remote_transcript_rx: Receiver<String> = remote
+        .as_ref()
+        .map(|r| r.transcript_rx.clone())
+        .unwrap_or_else(crossbeam_channel::never);
+    // Whether the latest audio frame, and any frame of the current
+    // utterance, came from a remote peer; such an utterance's transcript is
+    // handled as `TranscriptOrigin::Remote`.
+    let mut last_frame_remote = false;
+    let mut utterance_remote = false;
+
     // Optional: dump recorded audio for debugging, controlled by env var.
     // Example: export BTWD_DEBUG_AUDIO_DIR=/tmp/btwd-audio
     let debug_audio_dir: Option<PathBuf> = std::env::var("BTWD_DEBUG_AUDIO_DIR")
@@ -265,215 +517,314 @@ fn run() -> Result<()> {
         eprintln!("debug: BTWD_DEBUG_AUDIO_DIR enabled: {}", dir.display());
     }
 
-    loop {
-        // Confirmation polling happens ONLY when the Executor has a pending command.
-        // The UI helper writes 'yes'/'no' into $XDG_RUNTIME_DIR/btwd-confirm-<request_id>.
-        if let Some(req_id) = exec.pending_request_id().map(|s| s.to_string()) {
-            let runtime_dir = std::env::var("XDG_RUNTIME_DIR").unwrap_or_else(|_| "/tmp".to_string());
-            let path = std::path::Path::new(&runtime_dir).join(format!("btwd-confirm-{}", req_id));
-            if let Ok(action) = std::fs::read_to_string(&path) {
-                let _ = std::fs::remove_file(&path);
-                let action = action.trim().to_ascii_lowercase();
-                if action == "no" {
-                    eprintln!("exec: cancel via notification");
-                    let _ = exec.cancel_pending("user canceled");
-                    // Best-effort: ensure no stale spool survives.
-                    let _ = std::fs::remove_file(&path);
-                    pending_confirm_request_id = None;
-                } else if action == "yes" {
-                    eprintln!("exec: confirm via notification");
-                    let _ = exec.confirm_pending();
+    'events: loop {
+        let event = select! {
+            recv(audio_rx) -> msg => match msg {
+                Ok(frame) => { last_frame_remote = false; Event::Audio(frame) }
+                Err(_) => Event::Shutdown,
+            },
+            recv(remote_audio_rx) -> msg => match msg {
+                Ok(frame) => { last_frame_remote = true; Event::Audio(frame) }
+                Err(_) => continue 'events,
+            },
+            recv(remote_transcript_rx) -> msg => match msg {
+                Ok(text) => Event::RemoteTranscript(text),
+                Err(_) => continue 'events,
+            },
+            recv(tick_rx) -> _ => Event::Tick,
+            recv(confirm_rx) -> msg => match msg {
+                Ok((id, yes)) => Event::Confirm { id, yes },
+                Err(_) => continue 'events,
+            },
+            recv(shutdown_rx) -> _ => Event::Shutdown,
+        };
+
+        match event {
+            Event::Shutdown => {
+                eprintln!("btwd: shutting down");
+                break 'events;
+            }
+
+            Event::Confirm { id, yes } => {
+                if exec.pending_request_id() == Some(id.as_str()) {
+                    let status = if yes { exec.confirm_pending() } else { exec.cancel_pending("user action") };
+                    eprintln!("exec: confirmation action -> {:?}", status);
                     pending_confirm_request_id = None;
                 }
-            } else {
-                let should_notify = pending_confirm_request_id.as_deref() != Some(&req_id);
-                if should_notify {
-                    pending_confirm_request_id = Some(req_id.clone());
-                    ui::notify_confirm_actions(cfg.ui.osd, &req_id, "btwd", "Confirm command");
-                }
             }
-        } else {
-            pending_confirm_request_id = None;
-        }
 
-        let frame = rx.recv().map_err(|_| {
-            BtwError::ParseError {
-                path: config_path.clone(),
-                kind: "audio",
-                message: "audio stream ended".into(),
+            Event::RemoteTranscript(text) => {
+                eprintln!("remote: transcript='{}'", text);
+                handle_transcript(
+                    &text,
+                    &cfg,
+                    &mut exec,
+                    &intent_router,
+                    &llm_client,
+                    &online,
+                    &mut deferred_question,
+                    TranscriptOrigin::Remote,
+                    remote.as_ref().map(|r| &r.answer_tx),
+                );
             }
-        })?;
 
-        // Ticks should be serviced regardless of audio state.
-        exec.handle_tick(Instant::now());
+            Event::Tick => {
+                // Fire the interactive confirmation notification once per
+                // pending command; its Confirm/Cancel click callback posts
+                // straight into `confirm_tx` instead of an external helper
+                // writing a spool file for us to poll.
+                if let Some(req_id) = exec.pending_request_id().map(|s| s.to_string()) {
+                    let should_notify = pending_confirm_request_id.as_deref() != Some(req_id.as_str());
+                    if should_notify {
+                        pending_confirm_request_id = Some(req_id.clone());
+                        let cb_tx = confirm_tx.clone();
+                        let cb_id = req_id.clone();
+                        ui::notify_confirm_actions(cfg.ui.osd, "btwd", "Confirm command", move |yes| {
+                            let _ = cb_tx.send((cb_id, yes));
+                        });
+                    }
+                } else {
+                    pending_confirm_request_id = None;
+                }
 
-        // Periodic heartbeat so it's obvious we're alive while idle.
-        if matches!(state, ListenState::Idle) && last_heartbeat.elapsed() >= Duration::from_secs(30) {
-            eprintln!("Listening for wake word...");
-            last_heartbeat = Instant::now();
-        }
+                // Expires a pending confirmation that's outlived its timeout,
+                // serviced here so it isn't tied to audio frame arrival.
+                if let Some(status) = exec.handle_tick(Instant::now()) {
+                    eprintln!("exec: {:?}", status);
+                }
 
-        match state {
-            ListenState::Idle => {
-                // Wake word detection.
-                if porcupine.process(&frame)? {
-                    eprintln!("wake: detected (porcupine)");
-                    // Single source of truth: notification only on Idle -> Listening.
-                    ui::notify_listening(cfg.ui.osd, cfg.ui.osd_timeout_ms);
-
-                    // Do NOT reuse this frame as user speech.
-                    state = ListenState::Listening;
-                    // Legacy manager wake handling removed from runtime path.
-                    samples.clear();
-                    silence_ms = 0.0;
-                    start_time = None;
-                    saw_post_wake_speech = false;
-                    eprintln!("state: Idle -> Listening (armed, waiting for speech)");
+                // Periodic connectivity probe. On a state transition, tell the user
+                // passively so a reconnect doesn't look like the assistant is just
+                // broken; on regaining connectivity, replay any deferred question.
+                if Instant::now() >= next_probe_at {
+                    // Snapshot the state from *before* this probe: surfacing
+                    // "Reconnecting…" for the probe's duration means state
+                    // briefly passes through `Connecting` on every retry
+                    // while offline, which would otherwise make `prev` (as
+                    // returned by `mark_online`/`mark_offline` below) differ
+                    // from `now` every single cycle and spam the transition
+                    // message instead of showing it once per real transition.
+                    let prev = online.state();
+                    if matches!(prev, net::IsOnline::Offline) {
+                        online.mark_connecting();
+                    }
+                    let probe_ok = net::has_internet(800);
+                    let next_delay = if probe_ok {
+                        online.mark_online();
+                        Duration::from_secs(30)
+                    } else {
+                        online.mark_offline().1
+                    };
+                    let now = online.state();
+                    if prev != now {
+                        let msg = match now {
+                            net::IsOnline::Online => "Back online.",
+                            net::IsOnline::Offline => "Lost internet connection, reconnecting…",
+                            net::IsOnline::Connecting => "Reconnecting…",
+                        };
+                        ui::notify_text(cfg.ui.osd, cfg.ui.osd_timeout_ms, "Btw", msg);
+                    }
+
+                    next_probe_at = Instant::now() + next_delay;
+
+                    if probe_ok {
+                        if let Some(question) = deferred_question.take() {
+                            eprintln!("assistant: back online, replaying deferred question");
+                            handle_transcript(&question, &cfg, &mut exec, &intent_router, &llm_client, &online, &mut deferred_question, TranscriptOrigin::Local, None);
+                        }
+                    }
+                }
+
+                // Periodic heartbeat so it's obvious we're alive while idle.
+                if matches!(state, ListenState::Idle) && last_heartbeat.elapsed() >= Duration::from_secs(30) {
+                    eprintln!("Listening for wake word...");
+                    last_heartbeat = Instant::now();
                 }
-                continue;
             }
-            ListenState::Listening => {
-                // We're "armed" after wake word. We start recording only once we see actual speech.
-                // This prevents the wake-word tail from being fed to ASR/UI/routing.
-
-                // Allow re-wake while armed (useful if we got stuck waiting for speech).
-                if porcupine.process(&frame)? {
-                    eprintln!("wake: detected again while Listening (re-arming)");
-                    ui::notify_listening(cfg.ui.osd, cfg.ui.osd_timeout_ms);
-                    samples.clear();
-                    silence_ms = 0.0;
-                    start_time = None;
-                    saw_post_wake_speech = false;
-                    last_listening_debug = Instant::now();
-                    continue;
+
+            Event::Audio(frame) => {
+                if !matches!(state, ListenState::Idle) {
+                    utterance_remote |= last_frame_remote;
+                }
+                match state {
+                    ListenState::Idle => {
+                        // Wake word detection.
+                        if porcupine.process(&frame)? {
+                            eprintln!("wake: detected (porcupine)");
+                            wake_detected_at = Some(Instant::now());
+                            utterance_remote = last_frame_remote;
+                            // Single source of truth: notification only on Idle -> Listening.
+                            ui::notify_listening(cfg.ui.osd, cfg.ui.osd_timeout_ms);
+
+                            // Do NOT reuse this frame as user speech.
+                            state = ListenState::Listening;
+                            // Legacy manager wake handling removed from runtime path.
+                            samples.clear();
+                            silence_ms = 0.0;
+                            start_time = None;
+                            saw_post_wake_speech = false;
+                            eprintln!("state: Idle -> Listening (armed, waiting for speech)");
+                        }
+                        continue 'events;
+                    }
+                    ListenState::Listening => {
+                        // We're "armed" after wake word. We start recording only once we see actual speech.
+                        // This prevents the wake-word tail from being fed to ASR/UI/routing.
+
+                        // Allow re-wake while armed (useful if we got stuck waiting for speech).
+                        if porcupine.process(&frame)? {
+                            eprintln!("wake: detected again while Listening (re-arming)");
+                            wake_detected_at = Some(Instant::now());
+                            ui::notify_listening(cfg.ui.osd, cfg.ui.osd_timeout_ms);
+                            samples.clear();
+                            silence_ms = 0.0;
+                            start_time = None;
+                            saw_post_wake_speech = false;
+                            last_listening_debug = Instant::now();
+                            continue 'events;
+                        }
+
+                        let sum_sq: f64 = frame.iter().map(|&s| {
+                            let v = s as f64;
+                            v * v
+                        }).sum();
+                        let rms = (sum_sq / frame_length as f64).sqrt() / i16::MAX as f64;
+
+                        let vad_speech = vad.is_speech(&frame);
+                        // Fallback: treat sufficiently loud audio as speech onset.
+                        // This uses the existing configured silence threshold.
+                        let rms_speech = rms >= cfg.speech.silence_threshold as f64;
+                        let speech = vad_speech || rms_speech;
+
+                        // Debug every ~2s while waiting for speech so we can confirm if VAD is firing.
+                        if last_listening_debug.elapsed() >= Duration::from_secs(2) {
+                            eprintln!(
+                                "listening: awaiting speech (vad_speech={}, rms_speech={}, rms={:.4}, vad_mode={})",
+                                vad_speech,
+                                rms_speech,
+                                rms,
+                                cfg.speech.vad_mode
+                            );
+                            last_listening_debug = Instant::now();
+                        }
+
+                        if speech {
+                            state = ListenState::Recording;
+                            // Legacy manager deciding state removed from runtime path.
+                            samples.clear();
+                            silence_ms = 0.0;
+                            start_time = Some(Instant::now());
+                            saw_post_wake_speech = true;
+                            samples.extend_from_slice(&frame);
+                            eprintln!("speech: detected (vad) -> start recording");
+                            eprintln!("state: Listening -> Recording");
+                        }
+                        continue 'events;
+                    }
+                    ListenState::Recording => {
+                        // Keep buffering audio during recording.
+                        samples.extend_from_slice(&frame);
+                    }
                 }
 
+                // RMS (existing logic)
                 let sum_sq: f64 = frame.iter().map(|&s| {
                     let v = s as f64;
                     v * v
                 }).sum();
                 let rms = (sum_sq / frame_length as f64).sqrt() / i16::MAX as f64;
 
-                let vad_speech = vad.is_speech(&frame);
-                // Fallback: treat sufficiently loud audio as speech onset.
-                // This uses the existing configured silence threshold.
-                let rms_speech = rms >= cfg.speech.silence_threshold as f64;
-                let speech = vad_speech || rms_speech;
+                let speech = vad.is_speech(&frame);
 
-                // Debug every ~2s while waiting for speech so we can confirm if VAD is firing.
-                if last_listening_debug.elapsed() >= Duration::from_secs(2) {
-                    eprintln!(
-                        "listening: awaiting speech (vad_speech={}, rms_speech={}, rms={:.4}, vad_mode={})",
-                        vad_speech,
-                        rms_speech,
-                        rms,
-                        cfg.speech.vad_mode
-                    );
-                    last_listening_debug = Instant::now();
-                }
-
-                if speech {
-                    state = ListenState::Recording;
-                    // Legacy manager deciding state removed from runtime path.
-                    samples.clear();
+                if !speech && rms < cfg.speech.silence_threshold as f64 {
+                    silence_ms += frame_ms;
+                } else {
                     silence_ms = 0.0;
-                    start_time = Some(Instant::now());
-                    saw_post_wake_speech = true;
-                    samples.extend_from_slice(&frame);
-                    eprintln!("speech: detected (vad) -> start recording");
-                    eprintln!("state: Listening -> Recording");
                 }
-                continue;
-            }
-            ListenState::Recording => {
-                // Keep buffering audio during recording.
-                samples.extend_from_slice(&frame);
-            }
-        }
 
-        // RMS (existing logic)
-        let sum_sq: f64 = frame.iter().map(|&s| {
-            let v = s as f64;
-            v * v
-        }).sum();
-        let rms = (sum_sq / frame_length as f64).sqrt() / i16::MAX as f64;
-
-        let speech = vad.is_speech(&frame);
-
-        if !speech && rms < cfg.speech.silence_threshold as f64 {
-            silence_ms += frame_ms;
-        } else {
-            silence_ms = 0.0;
-        }
+                let elapsed = start_time.map(|t| t.elapsed().as_secs_f64()).unwrap_or(0.0);
 
-        let elapsed = start_time.map(|t| t.elapsed().as_secs_f64()).unwrap_or(0.0);
+                if silence_ms >= cfg.speech.silence_duration_ms as f64 ||
+                   elapsed >= cfg.speech.max_utterance_seconds as f64 {
 
-        if silence_ms >= cfg.speech.silence_duration_ms as f64 ||
-           elapsed >= cfg.speech.max_utterance_seconds as f64 {
+                    eprintln!(
+                        "recording: stop (samples={}, elapsed_sec={:.2}, silence_ms={:.0})",
+                        samples.len(),
+                        elapsed,
+                        silence_ms
+                    );
 
-            eprintln!(
-                "recording: stop (samples={}, elapsed_sec={:.2}, silence_ms={:.0})",
-                samples.len(),
-                elapsed,
-                silence_ms
-            );
+                    // Optionally dump captured audio to disk for debugging.
+                    if let Some(dir) = &debug_audio_dir {
+                        if let Err(e) = std::fs::create_dir_all(dir) {
+                            eprintln!("debug: failed to create BTWD_DEBUG_AUDIO_DIR: {}", e);
+                        } else {
+                            let ts = std::time::SystemTime::now()
+                                .duration_since(std::time::UNIX_EPOCH)
+                                .unwrap_or_default()
+                                .as_millis();
+                            let path = dir.join(format!("btwd-{}.wav", ts));
+                            let wav_bytes = audio::encode_wav(&samples, sample_rate);
+                            match std::fs::write(&path, &wav_bytes) {
+                                Ok(_) => eprintln!("debug: audio saved: {}", path.display()),
+                                Err(e) => eprintln!("debug: failed to save audio: {}", e),
+                            }
 
-            // Optionally dump captured audio to disk for debugging.
-            if let Some(dir) = &debug_audio_dir {
-                if let Err(e) = std::fs::create_dir_all(dir) {
-                    eprintln!("debug: failed to create BTWD_DEBUG_AUDIO_DIR: {}", e);
-                } else {
-                    let ts = std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .unwrap_or_default()
-                        .as_millis();
-                    let path = dir.join(format!("btwd-{}.pcm16", ts));
-                    let bytes: Vec<u8> = samples
-                        .iter()
-                        .flat_map(|s| s.to_le_bytes())
-                        .collect();
-                    match std::fs::write(&path, bytes) {
-                        Ok(_) => eprintln!("debug: audio saved: {}", path.display()),
-                        Err(e) => eprintln!("debug: failed to save audio: {}", e),
+                            // Optionally log a base64 snippet of the capture for remote
+                            // diagnostics, capped so logs don't balloon on long utterances.
+                            if std::env::var("BTWD_DEBUG_AUDIO_BASE64").ok().as_deref() == Some("1") {
+                                let snippet_len = wav_bytes.len().min(32 * 1024);
+                                let encoded = base64::engine::general_purpose::STANDARD.encode(&wav_bytes[..snippet_len]);
+                                eprintln!("debug: audio base64 (first {} bytes): {}", snippet_len, encoded);
+                            }
+                        }
                     }
-                }
-            }
 
-            // Only attempt ASR if we actually transitioned to Recording because we saw speech.
-            // (This should always be true in Recording state, but keep the invariant explicit.)
-            if saw_post_wake_speech && !samples.is_empty() {
-                eprintln!("asr: sending audio to worker");
-                match worker.transcribe(samples.clone(), sample_rate) {
-                    Ok(resp) => {
-                        if let Some(err) = resp.error.as_deref() {
-                            if !err.is_empty() {
-                                eprintln!("asr: worker returned error: {}", err);
+                    // Only attempt ASR if we actually transitioned to Recording because we saw speech.
+                    // (This should always be true in Recording state, but keep the invariant explicit.)
+                    if saw_post_wake_speech && !samples.is_empty() {
+                        eprintln!("asr: sending audio to worker");
+                        match worker.transcribe(samples.clone(), sample_rate) {
+                            Ok(resp) => {
+                                if let Some(err) = resp.error.as_deref() {
+                                    if !err.is_empty() {
+                                        eprintln!("asr: worker returned error: {}", err);
+                                    }
+                                }
+                                let raw_text = resp.text;
+                                let text = raw_text.trim();
+                                eprintln!("asr: text='{}'", raw_text);
+                                if let Some(wake_at) = wake_detected_at.take() {
+                                    eprintln!("latency: wake-to-transcript {:.0}ms", wake_at.elapsed().as_secs_f64() * 1000.0);
+                                }
+
+                                // Never show a transcript for the wake word alone; this is post-wake speech only.
+                                ui::notify_text(cfg.ui.osd, cfg.ui.osd_timeout_ms, "You", text);
+
+                                // Centralized strict decision logic: exactly one path.
+                                let origin = if utterance_remote { TranscriptOrigin::Remote } else { TranscriptOrigin::Local };
+                                handle_transcript(text, &cfg, &mut exec, &intent_router, &llm_client, &online, &mut deferred_question, origin, remote.as_ref().map(|r| &r.answer_tx));
                             }
+                            Err(e) => eprintln!("ASR error: {}", e),
                         }
-                        let raw_text = resp.text;
-                        let text = raw_text.trim();
-                        eprintln!("asr: text='{}'", raw_text);
-
-                        // Never show a transcript for the wake word alone; this is post-wake speech only.
-                        ui::notify_text(cfg.ui.osd, cfg.ui.osd_timeout_ms, "You", text);
-
-                        // Centralized strict decision logic: exactly one path.
-                        handle_transcript(text, &cfg, &mut exec, &intent_router, &llm_client);
+                    } else {
+                        eprintln!("asr: skipped (no post-wake speech captured)");
+                        wake_detected_at = None;
                     }
-                    Err(e) => eprintln!("ASR error: {}", e),
+
+                    state = ListenState::Idle;
+                    samples.clear();
+                    silence_ms = 0.0;
+                    start_time = None;
+                    saw_post_wake_speech = false;
+                    utterance_remote = false;
+                    eprintln!("state: -> Idle");
                 }
-            } else {
-                eprintln!("asr: skipped (no post-wake speech captured)");
             }
-
-            state = ListenState::Idle;
-            samples.clear();
-            silence_ms = 0.0;
-            start_time = None;
-            saw_post_wake_speech = false;
-            eprintln!("state: -> Idle");
         }
     }
+
+    Ok(())
 }
 
 fn expected_missing(xdg: &BaseDirectories, filename: &str, kind: &'static str) -> BtwError {
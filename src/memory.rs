@@ -0,0 +1,123 @@
+//! Local retrieval-augmented memory: a persistent on-disk knowledge base of
+//! user-ingested notes/documents, chunked and embedded so `search` can try
+//! "have I already been told this?" before ever reaching Tavily.
+//!
+//! Storage is a single JSON file of `{text, embedding}` entries, read/parsed
+//! by hand via `serde_json::Value` the same way `search`/`llm` handle wire
+//! JSON rather than deriving typed structs for it.
+
+use crate::llm::LlmClient;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Rough ~500-token passage size, approximated by word count since nothing
+/// in this crate carries a tokenizer; close enough to keep each chunk
+/// coherent and cheap to embed.
+const CHUNK_WORDS: usize = 375;
+
+#[derive(Debug, Clone)]
+pub struct MemoryChunk {
+    pub text: String,
+    pub embedding: Vec<f32>,
+}
+
+/// Loaded knowledge base for a single query/ingest call. Callers re-load
+/// from disk each time rather than holding it open across calls.
+pub struct MemoryStore {
+    path: PathBuf,
+    chunks: Vec<MemoryChunk>,
+}
+
+impl MemoryStore {
+    pub fn load(path: &Path) -> Self {
+        let chunks = std::fs::read_to_string(path)
+            .ok()
+            .and_then(|raw| serde_json::from_str::<Value>(&raw).ok())
+            .map(|val| parse_chunks(&val))
+            .unwrap_or_default();
+        Self { path: path.to_path_buf(), chunks }
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let entries: Vec<Value> = self
+            .chunks
+            .iter()
+            .map(|c| serde_json::json!({ "text": c.text, "embedding": c.embedding }))
+            .collect();
+        let raw = serde_json::to_string_pretty(&Value::Array(entries)).map_err(|e| e.to_string())?;
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        std::fs::write(&self.path, raw).map_err(|e| e.to_string())
+    }
+
+    /// Chunks `text` into ~500-token passages, embeds each via `llm`, and
+    /// appends them to the store. Returns the number of chunks added.
+    pub fn ingest(&mut self, text: &str, llm: &Arc<dyn LlmClient>) -> Result<usize, String> {
+        let mut added = 0;
+        for chunk in chunk_text(text) {
+            let embedding = llm.embed(&chunk)?;
+            self.chunks.push(MemoryChunk { text: chunk, embedding });
+            added += 1;
+        }
+        self.save()?;
+        Ok(added)
+    }
+
+    /// Returns up to `k` stored chunks whose cosine similarity to
+    /// `query_embedding` is at least `threshold`, most similar first.
+    pub fn top_k(&self, query_embedding: &[f32], k: usize, threshold: f32) -> Vec<&MemoryChunk> {
+        let mut scored: Vec<(f32, &MemoryChunk)> = self
+            .chunks
+            .iter()
+            .map(|c| (cosine_similarity(query_embedding, &c.embedding), c))
+            .filter(|(score, _)| *score >= threshold)
+            .collect();
+        scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+        scored.truncate(k);
+        scored.into_iter().map(|(_, c)| c).collect()
+    }
+}
+
+fn parse_chunks(val: &Value) -> Vec<MemoryChunk> {
+    val.as_array()
+        .map(|entries| {
+            entries
+                .iter()
+                .filter_map(|entry| {
+                    let text = entry.get("text")?.as_str()?.to_string();
+                    let embedding = entry
+                        .get("embedding")?
+                        .as_array()?
+                        .iter()
+                        .filter_map(|v| v.as_f64().map(|f| f as f32))
+                        .collect();
+                    Some(MemoryChunk { text, embedding })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn chunk_text(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .chunks(CHUNK_WORDS)
+        .map(|words| words.join(" "))
+        .filter(|s| !s.trim().is_empty())
+        .collect()
+}
+
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.is_empty() || a.len() != b.len() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a * norm_b)
+}
@@ -1,5 +1,6 @@
 use std::net::{Ipv4Addr, SocketAddr, TcpStream};
-use std::time::Duration;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// Best-effort connectivity probe.
 ///
@@ -9,3 +10,110 @@ pub fn has_internet(timeout_ms: u64) -> bool {
     let addr = SocketAddr::from((Ipv4Addr::new(1, 1, 1, 1), 53));
     TcpStream::connect_timeout(&addr, Duration::from_millis(timeout_ms)).is_ok()
 }
+
+/// Coarse connectivity state for gating/deferring LLM calls, rather than
+/// treating every blip as a hard failure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IsOnline {
+    Online,
+    Offline,
+    Connecting,
+}
+
+const BACKOFF_BASE_MS: u64 = 500;
+const BACKOFF_MAX_MS: u64 = 30_000;
+
+/// Exponential-backoff schedule: 500ms doubling up to a 30s cap, with
+/// jitter so a fleet of retries doesn't all line up on the same tick.
+pub struct Backoff {
+    attempt: u32,
+}
+
+impl Backoff {
+    pub fn new() -> Self {
+        Self { attempt: 0 }
+    }
+
+    /// Returns the delay to wait before the next retry and advances the
+    /// schedule. Call `reset` after a successful attempt.
+    pub fn next_delay(&mut self) -> Duration {
+        let exp = BACKOFF_BASE_MS.saturating_mul(1u64 << self.attempt.min(16));
+        let capped = exp.min(BACKOFF_MAX_MS);
+        self.attempt += 1;
+
+        // +/-20% jitter so simultaneous retries don't thundering-herd.
+        let jitter_span = capped / 5;
+        let jitter = if jitter_span > 0 {
+            (pseudo_random_u64() % (jitter_span * 2)) as i64 - jitter_span as i64
+        } else {
+            0
+        };
+        let delayed = (capped as i64 + jitter).max(BACKOFF_BASE_MS as i64) as u64;
+        Duration::from_millis(delayed)
+    }
+
+    pub fn reset(&mut self) {
+        self.attempt = 0;
+    }
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Cheap, dependency-free jitter source (we don't need cryptographic
+/// randomness, just spread). `Instant::now().elapsed()` immediately after
+/// creation only measures the gap to the next clock read (tens of ns), which
+/// barely varies call to call; wall-clock nanos actually drift across calls
+/// and give the spread this is meant to provide.
+fn pseudo_random_u64() -> u64 {
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_nanos() as u64).unwrap_or(0);
+    nanos ^ 0x9E3779B97F4A7C15
+}
+
+/// Tracks whether the daemon currently believes it's online, and the
+/// backoff schedule to use for the next reconnect attempt. Shared via `Arc`
+/// within the main loop; only the connectivity probe in `run()` updates it,
+/// and failed LLM requests do not change its state.
+pub struct OnlineTracker {
+    state: Mutex<IsOnline>,
+    backoff: Mutex<Backoff>,
+}
+
+impl OnlineTracker {
+    pub fn new() -> Self {
+        Self { state: Mutex::new(IsOnline::Connecting), backoff: Mutex::new(Backoff::new()) }
+    }
+
+    pub fn state(&self) -> IsOnline {
+        *self.state.lock().unwrap()
+    }
+
+    /// Records a successful request/probe, resetting the backoff schedule.
+    /// Returns the previous state so the caller can notify on a transition.
+    pub fn mark_online(&self) -> IsOnline {
+        let prev = std::mem::replace(&mut *self.state.lock().unwrap(), IsOnline::Online);
+        self.backoff.lock().unwrap().reset();
+        prev
+    }
+
+    /// Records a failed request/probe and returns the previous state plus
+    /// how long to wait before retrying.
+    pub fn mark_offline(&self) -> (IsOnline, Duration) {
+        let prev = std::mem::replace(&mut *self.state.lock().unwrap(), IsOnline::Offline);
+        let delay = self.backoff.lock().unwrap().next_delay();
+        (prev, delay)
+    }
+
+    pub fn mark_connecting(&self) -> IsOnline {
+        std::mem::replace(&mut *self.state.lock().unwrap(), IsOnline::Connecting)
+    }
+}
+
+impl Default for OnlineTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,248 @@
+//! Headless remote-control interface: an authenticated peer on another
+//! machine can stream Opus audio (decoded into the same `i16` frames the
+//! local microphone path produces) and/or submit text transcripts directly
+//! into the pipeline, the way `voice-bridge` drives its clients over tokio.
+//!
+//! Inbound audio/transcripts are merged into the main event loop via plain
+//! `crossbeam_channel`s (see `Event::Audio`/`Event::RemoteTranscript` in
+//! `main`); this module only owns the tokio runtime and the wire protocol.
+//! Outbound answers go through `RemoteAnswerSink`, the same `AnswerSink`
+//! abstraction `telegram::TelegramSink` uses, so `main::handle_transcript`
+//! can route a remote-origin question's finished answer back over the
+//! socket instead of showing a desktop notification/speaking on the host.
+//! Answers are still broadcast to every connected peer rather than routed
+//! to the specific connection that asked — true per-request routing would
+//! need the wire protocol to carry a request id, which is a bigger change
+//! than this fix.
+//!
+//! There's a single `cfg.shared_secret`/`cfg.allow_list` for the whole
+//! `RemoteCfg`, not one per peer, so "per-connection allow-list" here means
+//! every authenticated connection is independently checked against that one
+//! allow-list (still intersected with the local command allow-list in
+//! `main::handle_transcript`) rather than each peer getting its own list.
+//! Every caller able to authenticate is the same trust class by
+//! construction, since they all present the same token — scoping
+//! differently per peer would need the auth frame to carry a peer identity
+//! (e.g. a per-peer token mapped to its own allow-list in config), which is
+//! a wire-protocol change, not something `TAG_AUTH`'s single shared secret
+//! supports today.
+
+use crate::error::{BtwError, Result};
+use crate::search::{AnswerOutcome, AnswerSink};
+use crossbeam_channel::{Receiver, Sender};
+use opus::{Channels, Decoder as OpusDecoder};
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+const TAG_AUTH: u8 = 0x01;
+const TAG_AUDIO_OPUS: u8 = 0x02;
+const TAG_TRANSCRIPT: u8 = 0x03;
+
+const TAG_AUTH_OK: u8 = 0x10;
+const TAG_AUTH_FAILED: u8 = 0x11;
+const TAG_ANSWER_TEXT: u8 = 0x12;
+const TAG_ANSWER_AUDIO_OPUS: u8 = 0x13;
+
+const MAX_FRAME_BYTES: u32 = 1024 * 1024;
+
+/// Channels the main loop merges into its `select!`, plus the sink it uses
+/// to publish finished answers back out to connected peers.
+pub struct RemoteHandle {
+    pub audio_rx: Receiver<Vec<i16>>,
+    pub transcript_rx: Receiver<String>,
+    pub answer_tx: tokio::sync::broadcast::Sender<String>,
+    _runtime_thread: std::thread::JoinHandle<()>,
+}
+
+/// Starts the remote subsystem on its own tokio runtime/OS thread. Returns
+/// `Ok(None)` (not an error) when `cfg.enabled` is false, so callers can
+/// treat "remote control disabled" the same as "not compiled in".
+pub fn spawn(
+    cfg: crate::config::RemoteCfg,
+    target_sample_rate: u32,
+    frame_length: u32,
+) -> Result<Option<RemoteHandle>> {
+    if !cfg.enabled {
+        return Ok(None);
+    }
+
+    let (audio_tx, audio_rx) = crossbeam_channel::unbounded::<Vec<i16>>();
+    let (transcript_tx, transcript_rx) = crossbeam_channel::unbounded::<String>();
+    let (answer_tx, _) = tokio::sync::broadcast::channel::<String>(32);
+    let answer_tx_for_thread = answer_tx.clone();
+
+    let thread = std::thread::Builder::new()
+        .name("btwd-remote".into())
+        .spawn(move || {
+            let rt = match tokio::runtime::Builder::new_multi_thread().enable_all().build() {
+                Ok(rt) => rt,
+                Err(e) => {
+                    eprintln!("remote: failed to start tokio runtime: {}", e);
+                    return;
+                }
+            };
+            rt.block_on(accept_loop(
+                cfg,
+                target_sample_rate,
+                frame_length,
+                audio_tx,
+                transcript_tx,
+                answer_tx_for_thread,
+            ));
+        })
+        .map_err(|e| BtwError::AudioDeviceError { message: format!("spawn remote thread: {}", e) })?;
+
+    Ok(Some(RemoteHandle {
+        audio_rx,
+        transcript_rx,
+        answer_tx,
+        _runtime_thread: thread,
+    }))
+}
+
+async fn accept_loop(
+    cfg: crate::config::RemoteCfg,
+    target_sample_rate: u32,
+    frame_length: u32,
+    audio_tx: Sender<Vec<i16>>,
+    transcript_tx: Sender<String>,
+    answer_tx: tokio::sync::broadcast::Sender<String>,
+) {
+    let listener = match TcpListener::bind(&cfg.bind_addr).await {
+        Ok(l) => l,
+        Err(e) => {
+            eprintln!("remote: failed to bind '{}': {}", cfg.bind_addr, e);
+            return;
+        }
+    };
+    eprintln!("remote: listening on {}", cfg.bind_addr);
+
+    let cfg = Arc::new(cfg);
+
+    loop {
+        let (socket, peer) = match listener.accept().await {
+            Ok(pair) => pair,
+            Err(e) => {
+                eprintln!("remote: accept failed: {}", e);
+                continue;
+            }
+        };
+
+        let cfg = cfg.clone();
+        let audio_tx = audio_tx.clone();
+        let transcript_tx = transcript_tx.clone();
+        let answers = answer_tx.subscribe();
+
+        tokio::spawn(async move {
+            eprintln!("remote: connection from {}", peer);
+            if let Err(e) =
+                handle_connection(socket, &cfg, target_sample_rate, frame_length, audio_tx, transcript_tx, answers).await
+            {
+                eprintln!("remote: connection from {} ended: {}", peer, e);
+            }
+        });
+    }
+}
+
+async fn read_frame(stream: &mut TcpStream) -> std::io::Result<(u8, Vec<u8>)> {
+    let mut len_buf = [0u8; 4];
+    stream.read_exact(&mut len_buf).await?;
+    let len = u32::from_be_bytes(len_buf);
+    if len == 0 || len > MAX_FRAME_BYTES {
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "frame too large"));
+    }
+    let mut tag_buf = [0u8; 1];
+    stream.read_exact(&mut tag_buf).await?;
+    let mut payload = vec![0u8; (len - 1) as usize];
+    stream.read_exact(&mut payload).await?;
+    Ok((tag_buf[0], payload))
+}
+
+async fn write_frame(stream: &mut TcpStream, tag: u8, payload: &[u8]) -> std::io::Result<()> {
+    let len = (payload.len() as u32) + 1;
+    stream.write_all(&len.to_be_bytes()).await?;
+    stream.write_all(&[tag]).await?;
+    stream.write_all(payload).await?;
+    stream.flush().await
+}
+
+async fn handle_connection(
+    mut stream: TcpStream,
+    cfg: &crate::config::RemoteCfg,
+    target_sample_rate: u32,
+    frame_length: u32,
+    audio_tx: Sender<Vec<i16>>,
+    transcript_tx: Sender<String>,
+    mut answers: tokio::sync::broadcast::Receiver<String>,
+) -> std::io::Result<()> {
+    // The first frame must be the shared-secret token; everything else is
+    // rejected until auth succeeds.
+    let (tag, payload) = read_frame(&mut stream).await?;
+    let token_ok = tag == TAG_AUTH && payload == cfg.shared_secret.as_bytes();
+    if !token_ok {
+        let _ = write_frame(&mut stream, TAG_AUTH_FAILED, b"invalid token").await;
+        return Ok(());
+    }
+    write_frame(&mut stream, TAG_AUTH_OK, b"").await?;
+
+    let opus_sample_rate = cfg.opus_sample_rate;
+    let mut decoder = OpusDecoder::new(opus_sample_rate, Channels::Mono)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, format!("opus decoder: {}", e)))?;
+    // Generous upper bound: 120ms at the configured rate.
+    let mut pcm_buf = vec![0i16; (opus_sample_rate as usize / 1000) * 120];
+    // A single decoded Opus packet (commonly 20ms, well under
+    // Porcupine's usual frame length) rarely lines up with `frame_length`
+    // on its own, so the sub-frame remainder has to be carried across
+    // packets for this connection rather than dropped per-packet.
+    let mut residual: Vec<i16> = Vec::new();
+
+    loop {
+        tokio::select! {
+            frame = read_frame(&mut stream) => {
+                let (tag, payload) = frame?;
+                match tag {
+                    TAG_AUDIO_OPUS => {
+                        match decoder.decode(&payload, &mut pcm_buf, false) {
+                            Ok(n) => crate::audio::emit_frames(&audio_tx, &pcm_buf[..n], opus_sample_rate, target_sample_rate, frame_length, &mut residual),
+                            Err(e) => eprintln!("remote: opus decode error: {}", e),
+                        }
+                    }
+                    TAG_TRANSCRIPT => {
+                        match String::from_utf8(payload) {
+                            Ok(text) => { let _ = transcript_tx.send(text); }
+                            Err(e) => eprintln!("remote: non-utf8 transcript: {}", e),
+                        }
+                    }
+                    other => eprintln!("remote: ignoring unknown frame tag 0x{:02x}", other),
+                }
+            }
+            answer = answers.recv() => {
+                match answer {
+                    Ok(text) => write_frame(&mut stream, TAG_ANSWER_TEXT, text.as_bytes()).await?,
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Delivers a finished answer to every connected remote peer instead of
+/// the desktop OSD+TTS path, for questions whose `TranscriptOrigin` was
+/// `Remote`. Mirrors `telegram::TelegramSink`, just over the raw socket
+/// protocol instead of the Telegram HTTP API.
+pub struct RemoteAnswerSink {
+    pub answer_tx: tokio::sync::broadcast::Sender<String>,
+}
+
+impl AnswerSink for RemoteAnswerSink {
+    fn deliver(&self, outcome: AnswerOutcome) {
+        let _ = self.answer_tx.send(outcome.text_with_source());
+    }
+}
+
+#[allow(dead_code)]
+const _TAG_ANSWER_AUDIO_OPUS_RESERVED: u8 = TAG_ANSWER_AUDIO_OPUS;
@@ -1,9 +1,333 @@
 use crate::config::{SearchCfg, SpeechOutputCfg};
 use crate::llm::LlmClient;
+use crate::memory::MemoryStore;
 use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Number of local-memory chunks fed into `answer_with_memory`'s context prompt.
+const MEMORY_TOP_K: usize = 4;
+
+/// Minimum cosine similarity for a stored chunk to be considered relevant
+/// enough to answer from, rather than falling through to Tavily.
+const MEMORY_THRESHOLD: f32 = 0.75;
+
+/// Question prefix that forces skipping the knowledge-check and local-memory
+/// stages and going straight to Tavily, for when the user knows they want
+/// fresh web results rather than whatever static/local answer would win.
+const FORCE_WEB_PREFIX: &str = "[SEARCH]";
+
+/// Generic staleness-based cache keyed on a normalized query string. Shared
+/// by `answer_with_llm_if_known` (long TTL, static knowledge rarely changes)
+/// and `search_with_fallback` (short TTL, web results go stale fast) so
+/// re-asking or re-triggering the same question doesn't re-hit the LLM or
+/// the web, and still answers from cache if `has_internet` later fails.
+struct TtlCache<V: Clone> {
+    interval: Duration,
+    entries: Mutex<HashMap<String, (Instant, V)>>,
+}
+
+impl<V: Clone> TtlCache<V> {
+    fn new(interval: Duration) -> Self {
+        Self { interval, entries: Mutex::new(HashMap::new()) }
+    }
+
+    fn get_or_compute<F>(&self, key: &str, compute: F) -> Result<V, String>
+    where
+        F: FnOnce() -> Result<V, String>,
+    {
+        let now = Instant::now();
+        if let Some((stored_at, value)) = self.entries.lock().unwrap().get(key) {
+            if now.duration_since(*stored_at) <= self.interval {
+                eprintln!("cache: HIT key='{}'", key);
+                return Ok(value.clone());
+            }
+        }
+        eprintln!("cache: MISS key='{}'", key);
+        let value = compute()?;
+        self.entries.lock().unwrap().insert(key.to_string(), (now, value.clone()));
+        Ok(value)
+    }
+}
+
+/// Normalizes a query into a cache key: lowercased, trimmed, internal
+/// whitespace collapsed to single spaces.
+fn normalize_query(query: &str) -> String {
+    query.trim().split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+fn llm_knowledge_cache(interval: Duration) -> &'static TtlCache<KnownOrUnknown> {
+    static CACHE: OnceLock<TtlCache<KnownOrUnknown>> = OnceLock::new();
+    CACHE.get_or_init(|| TtlCache::new(interval))
+}
+
+fn search_cache(interval: Duration) -> &'static TtlCache<SearchResult> {
+    static CACHE: OnceLock<TtlCache<SearchResult>> = OnceLock::new();
+    CACHE.get_or_init(|| TtlCache::new(interval))
+}
+
+/// Which `SearchProvider` to try, in the order configured in
+/// `SearchCfg::providers`. Mirrors `tts::TtsBackend`'s fallback-chain shape:
+/// the daemon moves to the next entry on error or empty results instead of
+/// hard-depending on a single paid API staying up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProviderKind {
+    Tavily,
+    Searxng,
+    Brave,
+}
+
+/// One web-search backend. Each implementation turns its provider's wire
+/// format into the same flat `SearchResult` so `answer_with_tavily` doesn't
+/// need to know which one actually answered.
+pub trait SearchProvider: Send + Sync {
+    fn name(&self) -> &'static str;
+    fn search(&self, query: &str, cfg: &SearchCfg) -> Result<SearchResult, String>;
+}
+
+fn http_cfg(cfg: &SearchCfg) -> crate::http::HttpClientCfg {
+    crate::http::HttpClientCfg {
+        connect_timeout_ms: cfg.connect_timeout_ms,
+        request_timeout_ms: cfg.timeout_ms,
+        max_retries: cfg.max_retries,
+    }
+}
+
+/// Tavily's `/search` endpoint — the original, paid-API provider.
+struct TavilyProvider;
+
+impl SearchProvider for TavilyProvider {
+    fn name(&self) -> &'static str {
+        "tavily"
+    }
+
+    fn search(&self, query: &str, cfg: &SearchCfg) -> Result<SearchResult, String> {
+        let api_key = std::env::var("TAVILY_API_KEY")
+            .map_err(|_| "missing TAVILY_API_KEY".to_string())?;
+
+        let http_cfg = http_cfg(cfg);
+        let client = crate::http::shared_client(&http_cfg)?;
+
+        // Match required request shape:
+        // - Use `Authorization: Bearer <key>` header
+        // - Fields: query, include_answer="basic", search_depth="basic", country
+        let mut req_body = serde_json::json!({
+            "query": query,
+            "include_answer": "basic",
+            "search_depth": "basic"
+        });
+
+        if let Some(country) = cfg.country.as_deref() {
+            if !country.trim().is_empty() {
+                req_body["country"] = serde_json::Value::String(country.trim().to_string());
+            }
+        }
+
+        let builder = client
+            .post("https://api.tavily.com/search")
+            .header(reqwest::header::AUTHORIZATION, format!("Bearer {}", api_key))
+            .json(&req_body);
+        let resp = crate::http::send_with_retry(&builder, &http_cfg)?;
+
+        let status = resp.status();
+        let raw: Value = resp
+            .json()
+            .map_err(|e| format!("json decode (tavily): {}", e))?;
+
+        if !status.is_success() {
+            return Err(format!("tavily status: {} body={}", status, raw));
+        }
+
+        let facts_text = facts_from_results(raw.get("results").and_then(|r| r.as_array()));
+        if facts_text.is_empty() {
+            return Err("tavily returned no results".into());
+        }
+
+        Ok(SearchResult { raw, facts_text })
+    }
+}
+
+/// A self-hosted SearXNG instance's JSON API
+/// (`{base_url}/search?q=...&format=json`) — no API key, keeps queries off
+/// any third party the user doesn't run themselves.
+struct SearxngProvider;
+
+impl SearchProvider for SearxngProvider {
+    fn name(&self) -> &'static str {
+        "searxng"
+    }
+
+    fn search(&self, query: &str, cfg: &SearchCfg) -> Result<SearchResult, String> {
+        let base_url = cfg
+            .searxng_url
+            .as_deref()
+            .filter(|s| !s.trim().is_empty())
+            .ok_or("no searxng_url configured")?;
+
+        let http_cfg = http_cfg(cfg);
+        let client = crate::http::shared_client(&http_cfg)?;
+
+        let builder = client
+            .get(format!("{}/search", base_url.trim_end_matches('/')))
+            .query(&[("q", query), ("format", "json")]);
+        let resp = crate::http::send_with_retry(&builder, &http_cfg)?;
+
+        let status = resp.status();
+        let raw: Value = resp
+            .json()
+            .map_err(|e| format!("json decode (searxng): {}", e))?;
+
+        if !status.is_success() {
+            return Err(format!("searxng status: {} body={}", status, raw));
+        }
+
+        let facts_text = facts_from_results(raw.get("results").and_then(|r| r.as_array()));
+        if facts_text.is_empty() {
+            return Err("searxng returned no results".into());
+        }
+
+        Ok(SearchResult { raw, facts_text })
+    }
+}
+
+/// Brave Search's `/res/v1/web/search` endpoint.
+struct BraveProvider;
+
+impl SearchProvider for BraveProvider {
+    fn name(&self) -> &'static str {
+        "brave"
+    }
+
+    fn search(&self, query: &str, cfg: &SearchCfg) -> Result<SearchResult, String> {
+        let api_key = std::env::var("BRAVE_API_KEY")
+            .map_err(|_| "missing BRAVE_API_KEY".to_string())?;
+
+        let http_cfg = http_cfg(cfg);
+        let client = crate::http::shared_client(&http_cfg)?;
+
+        let mut builder = client
+            .get("https://api.search.brave.com/res/v1/web/search")
+            .header("X-Subscription-Token", api_key)
+            .header(reqwest::header::ACCEPT, "application/json")
+            .query(&[("q", query)]);
+        if let Some(country) = cfg.country.as_deref().filter(|s| !s.trim().is_empty()) {
+            builder = builder.query(&[("country", country)]);
+        }
+
+        let resp = crate::http::send_with_retry(&builder, &http_cfg)?;
+
+        let status = resp.status();
+        let raw: Value = resp
+            .json()
+            .map_err(|e| format!("json decode (brave): {}", e))?;
+
+        if !status.is_success() {
+            return Err(format!("brave status: {} body={}", status, raw));
+        }
+
+        let results = raw
+            .get("web")
+            .and_then(|w| w.get("results"))
+            .and_then(|r| r.as_array());
+        let facts_text = facts_from_results(results);
+        if facts_text.is_empty() {
+            return Err("brave returned no results".into());
+        }
+
+        Ok(SearchResult { raw, facts_text })
+    }
+}
+
+/// Flattens a provider's result array into compact "facts" text to pass to
+/// the LLM, tolerating each provider's own field naming (`content` for
+/// Tavily/SearXNG, `description` for Brave).
+fn facts_from_results(results: Option<&Vec<Value>>) -> String {
+    let mut lines: Vec<String> = Vec::new();
+
+    if let Some(results) = results {
+        for r in results {
+            let title = r.get("title").and_then(|v| v.as_str()).unwrap_or("").trim();
+            let url = r.get("url").and_then(|v| v.as_str()).unwrap_or("").trim();
+            let content = r
+                .get("content")
+                .or_else(|| r.get("description"))
+                .and_then(|v| v.as_str())
+                .unwrap_or("")
+                .trim();
+
+            let mut chunk = String::new();
+            if !title.is_empty() {
+                chunk.push_str(title);
+            }
+            if !url.is_empty() {
+                if !chunk.is_empty() {
+                    chunk.push_str(" — ");
+                }
+                chunk.push_str(url);
+            }
+            if !content.is_empty() {
+                if !chunk.is_empty() {
+                    chunk.push('\n');
+                }
+                chunk.push_str(content);
+            }
+
+            if !chunk.is_empty() {
+                lines.push(chunk);
+            }
+        }
+    }
+
+    lines.join("\n\n")
+}
+
+/// Builds the provider fallback chain in the exact order of `cfg.providers`.
+fn build_providers(kinds: &[ProviderKind]) -> Vec<Box<dyn SearchProvider>> {
+    kinds
+        .iter()
+        .map(|kind| -> Box<dyn SearchProvider> {
+            match kind {
+                ProviderKind::Tavily => Box::new(TavilyProvider),
+                ProviderKind::Searxng => Box::new(SearxngProvider),
+                ProviderKind::Brave => Box::new(BraveProvider),
+            }
+        })
+        .collect()
+}
+
+/// Tries each configured provider in order, moving to the next on error or
+/// empty results, and caches whichever one succeeds so a repeated query
+/// doesn't re-hit every provider in the chain.
+fn search_with_fallback(query: &str, cfg: &SearchCfg) -> Result<SearchResult, String> {
+    let key = normalize_query(query);
+    search_cache(Duration::from_secs(cfg.tavily_cache_ttl_secs)).get_or_compute(&key, || {
+        if !crate::net::has_internet(800) {
+            return Err("no internet connection".to_string());
+        }
+
+        let providers = build_providers(&cfg.providers);
+        if providers.is_empty() {
+            return Err("no search providers configured".to_string());
+        }
+
+        let mut last_err = "no providers tried".to_string();
+        for provider in &providers {
+            match provider.search(query, cfg) {
+                Ok(result) => {
+                    eprintln!("search: provider '{}' succeeded", provider.name());
+                    return Ok(result);
+                }
+                Err(e) => {
+                    eprintln!("search: provider '{}' failed ({}), trying next", provider.name(), e);
+                    last_err = e;
+                }
+            }
+        }
 
-const KNOWLEDGE_CHECK_SENTINEL: &str =
-    "I do not have enough up-to-date information to answer this.";
+        Err(format!("all search providers failed (last error: {})", last_err))
+    })
+}
 
 #[cfg(test)]
 mod tests {
@@ -34,14 +358,31 @@ mod tests {
         fn tts(&self, _text: &str) -> Result<Vec<u8>, String> {
             Err("not used".into())
         }
+
+        fn embed(&self, _text: &str) -> Result<Vec<f32>, String> {
+            Err("not used".into())
+        }
+
+        fn call_tools(
+            &self,
+            _text: &str,
+            _commands: &[crate::intent::IntentCommand],
+            _dispatch: &dyn Fn(&crate::llm::ToolCall) -> Result<String, String>,
+        ) -> Result<String, String> {
+            Err("not used".into())
+        }
+
+        fn answer_short_stream(&self, _prompt: &str, _on_delta: &mut dyn FnMut(&str)) -> Result<(), String> {
+            Err("not used".into())
+        }
     }
 
     #[test]
     fn knowledge_check_exact_sentinel_triggers_unknown() {
         let llm: Arc<dyn crate::llm::LlmClient> = Arc::new(StubLlm {
-            out: KNOWLEDGE_CHECK_SENTINEL.to_string(),
+            out: crate::i18n::active().message("knowledge-check-sentinel", None),
         });
-        let res = answer_with_llm_if_known("who won f1 2025", &llm).unwrap();
+        let res = answer_with_llm_if_known("who won f1 2025 unique cache key", &llm, Duration::from_secs(0)).unwrap();
         assert!(matches!(res, KnownOrUnknown::Unknown));
     }
 
@@ -50,11 +391,28 @@ mod tests {
         let llm: Arc<dyn crate::llm::LlmClient> = Arc::new(StubLlm {
             out: "I don't have real-time data".to_string(),
         });
-        let res = answer_with_llm_if_known("today's weather", &llm).unwrap();
+        let res = answer_with_llm_if_known("today's weather unique cache key", &llm, Duration::from_secs(0)).unwrap();
         assert!(matches!(res, KnownOrUnknown::Known(_)));
     }
+
+    #[test]
+    fn ttl_cache_returns_stale_after_interval_elapses() {
+        let cache = TtlCache::new(Duration::from_millis(0));
+        let calls = Mutex::new(0);
+        cache.get_or_compute("k", || {
+            *calls.lock().unwrap() += 1;
+            Ok::<_, String>(1)
+        }).unwrap();
+        std::thread::sleep(Duration::from_millis(1));
+        cache.get_or_compute("k", || {
+            *calls.lock().unwrap() += 1;
+            Ok::<_, String>(2)
+        }).unwrap();
+        assert_eq!(*calls.lock().unwrap(), 2);
+    }
 }
 
+#[derive(Clone)]
 enum KnownOrUnknown {
     Known(String),
     Unknown,
@@ -63,25 +421,61 @@ enum KnownOrUnknown {
 fn answer_with_llm_if_known(
     query: &str,
     llm: &std::sync::Arc<dyn LlmClient>,
+    cache_ttl: Duration,
 ) -> Result<KnownOrUnknown, String> {
-    // Stage 1: strict knowledge check.
-    // Must return the exact sentinel string if it cannot answer confidently from static knowledge.
-    let prompt = format!(
-        "You are an AI assistant named Bumblebee, running on arch linux (just like siri for mac).\n\nAnswer the user ONLY IF you are certain the answer is:\n- Not time-sensitive\n- Not dependent on real-time data\n- Not dependent on events after your training cutoff\n- Not dependent on current news, stock prices, sports results, weather, or recent events\n\nIf you can answer confidently from static knowledge, give the answer.\n\nIf you cannot answer confidently, respond with EXACTLY this sentence and nothing else:\n\n\"{}\"\n\nUser question:\n{}\n\nImportant: Never mention knowledge cutoff, training data, or that you are an AI language model.",
-        KNOWLEDGE_CHECK_SENTINEL,
-        query
-    );
-
-    let out = llm.answer_short(&prompt)?;
-    if out.trim() == KNOWLEDGE_CHECK_SENTINEL {
-        return Ok(KnownOrUnknown::Unknown);
-    }
+    let key = normalize_query(query);
+    llm_knowledge_cache(cache_ttl).get_or_compute(&key, || {
+        // Stage 1: strict knowledge check.
+        // Must return the exact sentinel string if it cannot answer confidently from static knowledge.
+        let locale = crate::i18n::active();
+        let sentinel = locale.message("knowledge-check-sentinel", None);
+        let mut args = fluent_bundle::FluentArgs::new();
+        args.set("sentinel", sentinel.clone());
+        args.set("query", query);
+        let prompt = locale.message("knowledge-check-prompt", Some(&args));
+
+        let out = llm.answer_short(&prompt)?;
+        if out.trim() == sentinel {
+            return Ok(KnownOrUnknown::Unknown);
+        }
 
-    let ans = out.trim();
-    if ans.is_empty() {
-        return Ok(KnownOrUnknown::Unknown);
+        let ans = out.trim();
+        if ans.is_empty() {
+            return Ok(KnownOrUnknown::Unknown);
+        }
+        Ok(KnownOrUnknown::Known(ans.to_string()))
+    })
+}
+
+/// Stage 2: local retrieval-augmented answer from `memory`'s on-disk knowledge
+/// base. Returns `Ok(None)` (rather than an error) when nothing in the store
+/// clears `MEMORY_THRESHOLD`, so the caller falls through to Tavily instead
+/// of surfacing "I don't know" from a store that simply has no match.
+fn answer_with_memory(
+    query: &str,
+    memory_path: &std::path::Path,
+    llm: &std::sync::Arc<dyn LlmClient>,
+) -> Result<Option<String>, String> {
+    let store = MemoryStore::load(memory_path);
+    let query_embedding = llm.embed(query)?;
+    let hits = store.top_k(&query_embedding, MEMORY_TOP_K, MEMORY_THRESHOLD);
+    if hits.is_empty() {
+        return Ok(None);
     }
-    Ok(KnownOrUnknown::Known(ans.to_string()))
+
+    let context = hits
+        .iter()
+        .map(|c| c.text.as_str())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    let locale = crate::i18n::active();
+    let mut args = fluent_bundle::FluentArgs::new();
+    args.set("query", query);
+    args.set("context", context);
+    let prompt = locale.message("memory-compose-prompt", Some(&args));
+
+    llm.answer_short(&prompt).map(Some)
 }
 
 fn answer_with_tavily(
@@ -89,198 +483,162 @@ fn answer_with_tavily(
     cfg: &SearchCfg,
     llm: &std::sync::Arc<dyn LlmClient>,
 ) -> Result<String, String> {
-    // Stage 2: Tavily -> facts-only Mistral compose.
-    let facts = tavily_search(query, cfg.timeout_ms, cfg.country.as_deref())?;
+    // Stage 3: web search (provider fallback chain) -> facts-only Mistral compose.
+    let facts = search_with_fallback(query, cfg)?;
 
-    let prompt = format!(
-        "User question:\n{}\n\nRetrieved web information:\n{}\n\nAnswer the question clearly and concisely using ONLY the information above.\nIf the information is insufficient or contradictory, say \"I don’t know.\"\n\nImportant: Never mention knowledge cutoff, training data, or that you are an AI language model.",
-        query,
-        facts.facts_text
-    );
+    let locale = crate::i18n::active();
+    let mut args = fluent_bundle::FluentArgs::new();
+    args.set("query", query);
+    args.set("context", facts.facts_text.as_str());
+    let prompt = locale.message("tavily-compose-prompt", Some(&args));
 
     llm.answer_short(&prompt)
 }
 
 #[derive(Debug, Clone)]
-pub struct TavilyResult {
+pub struct SearchResult {
     pub raw: Value,
     pub facts_text: String,
 }
 
+/// A finished answer, handed to whichever `AnswerSink` the caller wired in.
+/// `source_label` is one of `"mistral"` (static knowledge), `"memory"`
+/// (local RAG) or `"tavily"` (web search) — same labels `DesktopSink` has
+/// always shown. `google_fallback_url` is only set for web answers, mirroring
+/// when the desktop path used to show its "open in browser" action.
+pub struct AnswerOutcome {
+    pub text: String,
+    pub source_label: &'static str,
+    pub google_fallback_url: Option<String>,
+}
+
+impl AnswerOutcome {
+    /// `text` with the localized `source-label` line appended, shared by
+    /// every `AnswerSink` so the format can't drift between them.
+    pub fn text_with_source(&self) -> String {
+        let mut args = fluent_bundle::FluentArgs::new();
+        args.set("source", self.source_label.to_string());
+        let source_line = crate::i18n::active().message("source-label", Some(&args));
+        format!("{}\n\n{}", self.text, source_line)
+    }
+}
+
+/// Destination for a composed answer. `search_and_summarize_async` builds
+/// exactly one `AnswerOutcome` per question and hands it off here, so the
+/// desktop notify+TTS path and remote frontends like the Telegram bot share
+/// the same gating/compose core and only differ in how they deliver the
+/// result.
+pub trait AnswerSink: Send + Sync {
+    fn deliver(&self, outcome: AnswerOutcome);
+}
+
+/// The original desktop delivery: an OSD notification (with an "open in
+/// browser" action for web answers) plus spoken TTS.
+pub struct DesktopSink {
+    pub ui_enabled: bool,
+    pub ui_timeout_ms: u64,
+    pub tts: SpeechOutputCfg,
+}
+
+impl AnswerSink for DesktopSink {
+    fn deliver(&self, outcome: AnswerOutcome) {
+        let locale = crate::i18n::active();
+        let ui_text = outcome.text_with_source();
+
+        if self.ui_enabled {
+            // A finished answer needs longer on screen than the short-lived
+            // status notifications the rest of `ui` shows, regardless of
+            // how `cfg.ui.osd_timeout_ms` is tuned for those.
+            let answer_timeout_ms = self.ui_timeout_ms.max(15_000);
+            match &outcome.google_fallback_url {
+                Some(google_url) => crate::ui::notify_answer_with_open_in_browser(
+                    self.ui_enabled,
+                    answer_timeout_ms,
+                    "Btw",
+                    &ui_text,
+                    google_url,
+                ),
+                None => crate::ui::notify_answer(self.ui_enabled, answer_timeout_ms, "Btw", &ui_text),
+            }
+        }
+
+        // Speak the *Mistral-produced* answer only. Never speak raw Tavily facts.
+        let mut tts_force = self.tts.clone();
+        tts_force.enabled = true;
+        crate::tts::speak_async(outcome.text, tts_force, Some(locale.code().to_string()));
+    }
+}
+
 pub fn search_and_summarize_async(
     question: String,
     search_cfg: SearchCfg,
-    ui_enabled: bool,
-    ui_timeout_ms: u64,
-    tts: SpeechOutputCfg,
     llm: std::sync::Arc<dyn LlmClient>,
+    sink: std::sync::Arc<dyn AnswerSink>,
 ) {
     if !search_cfg.enabled {
         return;
     }
 
-    std::thread::spawn(move || {
-        let answer_timeout_ms = ui_timeout_ms.max(15_000);
+    // `[SEARCH]` forces straight-to-web, bypassing both the static-knowledge
+    // check and local memory, for when the user already knows they want
+    // fresh results rather than whatever the gated stages would pick.
+    let (question, force_web) = match question.trim().strip_prefix(FORCE_WEB_PREFIX) {
+        Some(rest) => (rest.trim().to_string(), true),
+        None => (question, false),
+    };
 
-        // For web results, abort early if offline.
-        // Important: do not call Tavily and do not fall back to any other web flow.
-        if !crate::net::has_internet(800) {
-            if ui_enabled {
-                crate::ui::notify_answer(
-                    ui_enabled,
-                    answer_timeout_ms,
-                    "Btw",
-                    "No internet connection. Cannot fetch web results.",
-                );
-            }
-            return;
-        }
+    std::thread::spawn(move || {
+        let llm_cache_ttl = Duration::from_secs(search_cfg.llm_cache_ttl_secs);
 
-        // Strict 2-stage gating:
+        // Strict gating:
         // 1) Ask LLM to answer only if it is certain (else return exact sentinel)
-        // 2) Only if sentinel, call Tavily and then ask LLM again using ONLY retrieved info
-        let (final_answer_res, source_label) = match answer_with_llm_if_known(&question, &llm) {
-            Ok(KnownOrUnknown::Known(ans)) => (Ok(ans), "mistral"),
-            Ok(KnownOrUnknown::Unknown) => (answer_with_tavily(&question, &search_cfg, &llm), "tavily"),
-            Err(e) => (Err(e), "tavily"),
+        // 2) Only if sentinel, try local memory retrieval (else return nothing above threshold)
+        // 3) Only if memory has no relevant chunks, call Tavily and ask LLM again using ONLY retrieved info
+        // `[SEARCH]` skips straight to step 3.
+        // Each of steps 1 and 3 checks its own TTL cache before doing real
+        // work, so a fresh cached answer still comes back even if
+        // `has_internet` would otherwise fail below.
+        let (final_answer_res, source_label) = if force_web {
+            (answer_with_tavily(&question, &search_cfg, &llm), "tavily")
+        } else {
+            match answer_with_llm_if_known(&question, &llm, llm_cache_ttl) {
+                Ok(KnownOrUnknown::Known(ans)) => (Ok(ans), "mistral"),
+                Ok(KnownOrUnknown::Unknown) => {
+                    match search_cfg.memory_path.as_ref() {
+                        Some(path) => match answer_with_memory(&question, path, &llm) {
+                            Ok(Some(ans)) => (Ok(ans), "memory"),
+                            Ok(None) => (answer_with_tavily(&question, &search_cfg, &llm), "tavily"),
+                            Err(e) => {
+                                eprintln!("memory stage error (falling back to tavily): {}", e);
+                                (answer_with_tavily(&question, &search_cfg, &llm), "tavily")
+                            }
+                        },
+                        None => (answer_with_tavily(&question, &search_cfg, &llm), "tavily"),
+                    }
+                }
+                Err(e) => (Err(e), "tavily"),
+            }
         };
 
-        match final_answer_res {
+        let outcome = match final_answer_res {
             Ok(answer) => {
-                if ui_enabled {
-                    let ui_text = format!("{}\n\n:source: {}", answer, source_label);
-
-                    if source_label == "tavily" {
-                        let google_url = format!(
-                            "https://www.google.com/search?q={}",
-                            urlencoding::encode(&question)
-                        );
-                        crate::ui::notify_answer_with_open_in_browser(
-                            ui_enabled,
-                            answer_timeout_ms,
-                            "Btw",
-                            &ui_text,
-                            &google_url,
-                        );
-                    } else {
-                        crate::ui::notify_answer(ui_enabled, answer_timeout_ms, "Btw", &ui_text);
-                    }
-                }
-                // Speak the *Mistral-produced* answer only. Never speak raw Tavily facts.
-                let mut tts_force = tts.clone();
-                tts_force.enabled = true;
-                crate::tts::speak_async(answer, tts_force);
+                let google_fallback_url = (source_label == "tavily").then(|| {
+                    format!("https://www.google.com/search?q={}", urlencoding::encode(&question))
+                });
+                AnswerOutcome { text: answer, source_label, google_fallback_url }
             }
             Err(e) => {
-                eprintln!("TAVILY error: {}", e);
-                let msg = "I couldn’t find reliable information.".to_string();
-                if ui_enabled {
-                    let ui_text = format!("{}\n\n:source: {}", msg, source_label);
-                    crate::ui::notify_answer(ui_enabled, answer_timeout_ms, "Btw", &ui_text);
-                }
-                let mut tts_force = tts;
-                tts_force.enabled = true;
-                crate::tts::speak_async(msg, tts_force);
+                eprintln!("search error: {}", e);
+                let locale = crate::i18n::active();
+                let msg = if !crate::net::has_internet(800) {
+                    locale.message("no-internet", None)
+                } else {
+                    locale.message("no-reliable-info", None)
+                };
+                AnswerOutcome { text: msg, source_label, google_fallback_url: None }
             }
-        }
-    });
-}
-
-pub fn tavily_search(query: &str, timeout_ms: u64, country: Option<&str>) -> Result<TavilyResult, String> {
-    let api_key = std::env::var("TAVILY_API_KEY")
-        .map_err(|_| "missing TAVILY_API_KEY".to_string())?;
-
-    let client = reqwest::blocking::Client::builder()
-        .timeout(std::time::Duration::from_millis(timeout_ms))
-        .build()
-        .map_err(|e| format!("client build: {}", e))?;
-
-    let url = "https://api.tavily.com/search";
+        };
 
-    // Match required request shape:
-    // - Use `Authorization: Bearer <key>` header
-    // - Fields: query, include_answer="basic", search_depth="basic", country
-    let mut req_body = serde_json::json!({
-        "query": query,
-        "include_answer": "basic",
-        "search_depth": "basic"
+        sink.deliver(outcome);
     });
-
-    if let Some(country) = country {
-        if !country.trim().is_empty() {
-            req_body["country"] = serde_json::Value::String(country.trim().to_string());
-        }
-    }
-
-    let resp = client
-        .post(url)
-        .header(reqwest::header::AUTHORIZATION, format!("Bearer {}", api_key))
-        .json(&req_body)
-        .send()
-        .map_err(|e| {
-            format!(
-                "http error (tavily search): connect={} timeout={} source={}",
-                e.is_connect(),
-                e.is_timeout(),
-                e
-            )
-        })?;
-
-    let status = resp.status();
-    let raw: Value = resp
-        .json()
-        .map_err(|e| format!("json decode (tavily): {}", e))?;
-
-    if !status.is_success() {
-        return Err(format!("tavily status: {} body={}", status, raw));
-    }
-
-    // Convert the result list into compact "facts" text to pass to Mistral.
-    let mut lines: Vec<String> = Vec::new();
-
-    if let Some(results) = raw.get("results").and_then(|r| r.as_array()) {
-        for r in results {
-            let title = r
-                .get("title")
-                .and_then(|v| v.as_str())
-                .unwrap_or("")
-                .trim();
-            let url = r.get("url").and_then(|v| v.as_str()).unwrap_or("").trim();
-            let content = r
-                .get("content")
-                .and_then(|v| v.as_str())
-                .unwrap_or("")
-                .trim();
-
-            let mut chunk = String::new();
-            if !title.is_empty() {
-                chunk.push_str(title);
-            }
-            if !url.is_empty() {
-                if !chunk.is_empty() {
-                    chunk.push_str(" — ");
-                }
-                chunk.push_str(url);
-            }
-            if !content.is_empty() {
-                if !chunk.is_empty() {
-                    chunk.push('\n');
-                }
-                chunk.push_str(content);
-            }
-
-            if !chunk.is_empty() {
-                lines.push(chunk);
-            }
-        }
-    }
-
-    if lines.is_empty() {
-        return Err("tavily returned no results".into());
-    }
-
-    Ok(TavilyResult {
-        raw,
-        facts_text: lines.join("\n\n"),
-    })
 }
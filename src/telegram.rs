@@ -0,0 +1,149 @@
+//! Telegram long-polling bot frontend: relays text messages from an
+//! allow-listed set of chat IDs through the same gating/compose core
+//! `search::search_and_summarize_async` uses for voice questions, replying
+//! with the composed answer instead of a desktop notification. This is the
+//! `AnswerSink` consumer `remote`'s module doc mentions — unlike `remote`,
+//! there's no audio here, just text in, text (plus an optional inline
+//! button) out.
+
+use crate::config::{SearchCfg, TelegramCfg};
+use crate::llm::LlmClient;
+use crate::search::{AnswerOutcome, AnswerSink};
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How long a single `getUpdates` call may block waiting for a new message,
+/// per Telegram's long-polling convention.
+const POLL_TIMEOUT_SECS: u64 = 30;
+
+/// Telegram's API puts the bot token in the URL path rather than a header,
+/// so `reqwest::Error`'s `Display` (which includes the failed request's URL)
+/// would otherwise leak it straight into stderr on every transient failure.
+fn redact_token(token: &str, err: impl std::fmt::Display) -> String {
+    err.to_string().replace(token, "***")
+}
+
+/// Starts the bot's poll loop on its own OS thread. Returns immediately;
+/// `cfg.enabled == false` is a no-op, the same "disabled is not an error"
+/// convention `remote::spawn` uses.
+pub fn spawn(cfg: TelegramCfg, search_cfg: SearchCfg, llm: Arc<dyn LlmClient>) {
+    if !cfg.enabled {
+        return;
+    }
+
+    let client = match reqwest::blocking::Client::builder()
+        // Request timeout must clear Telegram's own long-poll window, or
+        // every poll looks like a transient failure.
+        .timeout(Duration::from_secs(POLL_TIMEOUT_SECS + 10))
+        .build()
+    {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("telegram: failed to build http client: {}", e);
+            return;
+        }
+    };
+
+    std::thread::Builder::new()
+        .name("btwd-telegram".into())
+        .spawn(move || poll_loop(cfg, search_cfg, llm, client))
+        .map(|_| ())
+        .unwrap_or_else(|e| eprintln!("telegram: failed to spawn poll thread: {}", e));
+}
+
+fn poll_loop(cfg: TelegramCfg, search_cfg: SearchCfg, llm: Arc<dyn LlmClient>, client: reqwest::blocking::Client) {
+    eprintln!("telegram: starting long-poll loop");
+    let mut offset: i64 = 0;
+    let mut backoff = crate::net::Backoff::new();
+
+    loop {
+        let url = format!("https://api.telegram.org/bot{}/getUpdates", cfg.bot_token);
+        let resp = client
+            .get(&url)
+            .query(&[("timeout", POLL_TIMEOUT_SECS.to_string()), ("offset", offset.to_string())])
+            .send()
+            .and_then(|r| r.error_for_status())
+            .and_then(|r| r.json::<Value>());
+
+        let body = match resp {
+            Ok(body) => body,
+            Err(e) => {
+                let delay = backoff.next_delay();
+                eprintln!("telegram: getUpdates failed ({}), retrying in {:?}", redact_token(&cfg.bot_token, e), delay);
+                std::thread::sleep(delay);
+                continue;
+            }
+        };
+
+        let Some(updates) = body.get("result").and_then(|r| r.as_array()) else {
+            let delay = backoff.next_delay();
+            eprintln!("telegram: getUpdates response had no 'result' array (body={}), retrying in {:?}", body, delay);
+            std::thread::sleep(delay);
+            continue;
+        };
+        backoff.reset();
+
+        for update in updates {
+            if let Some(update_id) = update.get("update_id").and_then(|v| v.as_i64()) {
+                offset = offset.max(update_id + 1);
+            }
+            handle_update(update, &cfg, &search_cfg, &llm, &client);
+        }
+    }
+}
+
+fn handle_update(
+    update: &Value,
+    cfg: &TelegramCfg,
+    search_cfg: &SearchCfg,
+    llm: &Arc<dyn LlmClient>,
+    client: &reqwest::blocking::Client,
+) {
+    let Some(message) = update.get("message") else { return };
+    let Some(chat_id) = message.get("chat").and_then(|c| c.get("id")).and_then(|v| v.as_i64()) else {
+        return;
+    };
+    if !cfg.allow_list.contains(&chat_id) {
+        eprintln!("telegram: ignoring message from non-allow-listed chat {}", chat_id);
+        return;
+    }
+    let Some(text) = message.get("text").and_then(|v| v.as_str()).map(str::trim).filter(|s| !s.is_empty()) else {
+        return;
+    };
+
+    let sink: Arc<dyn AnswerSink> =
+        Arc::new(TelegramSink { bot_token: cfg.bot_token.clone(), chat_id, client: client.clone() });
+
+    crate::search::search_and_summarize_async(text.to_string(), search_cfg.clone(), llm.clone(), sink);
+}
+
+/// Replies in the originating chat instead of showing a desktop
+/// notification. Web answers get their Google fallback link as an inline
+/// button rather than the desktop "open in browser" notify-send action.
+struct TelegramSink {
+    bot_token: String,
+    chat_id: i64,
+    client: reqwest::blocking::Client,
+}
+
+impl AnswerSink for TelegramSink {
+    fn deliver(&self, outcome: AnswerOutcome) {
+        let text = outcome.text_with_source();
+
+        let mut body = serde_json::json!({
+            "chat_id": self.chat_id,
+            "text": text,
+        });
+        if let Some(google_url) = &outcome.google_fallback_url {
+            body["reply_markup"] = serde_json::json!({
+                "inline_keyboard": [[{ "text": "Open in browser", "url": google_url }]]
+            });
+        }
+
+        let url = format!("https://api.telegram.org/bot{}/sendMessage", self.bot_token);
+        if let Err(e) = self.client.post(&url).json(&body).send().and_then(|r| r.error_for_status()) {
+            eprintln!("telegram: sendMessage to chat {} failed: {}", self.chat_id, redact_token(&self.bot_token, e));
+        }
+    }
+}
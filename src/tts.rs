@@ -0,0 +1,309 @@
+use std::fmt;
+use std::process::{Command, Stdio};
+
+/// Per-utterance voice parameters. Any field a backend doesn't support
+/// (see `TtsBackend::supports_rate`/`supports_pitch`) is simply ignored.
+#[derive(Debug, Clone, Default)]
+pub struct SpeakOptions {
+    pub voice: Option<String>,
+    /// Roughly -100..100 (spd-say's own scale); backends with another unit,
+    /// such as espeak-ng's words per minute, convert from it.
+    pub rate: Option<i32>,
+    pub pitch: Option<i32>,
+    /// BCP-47 locale of `text` (e.g. `es-ES`), from `i18n`'s active locale.
+    /// Only consulted when `voice` isn't set explicitly, so a configured
+    /// voice always wins over guessing one from the language.
+    pub lang: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum TtsError {
+    NotInstalled { backend: &'static str },
+    SpawnFailed { backend: &'static str, source: std::io::Error },
+    ProcessFailed { backend: &'static str, status: Option<i32> },
+}
+
+impl fmt::Display for TtsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TtsError::NotInstalled { backend } => write!(f, "{} is not installed", backend),
+            TtsError::SpawnFailed { backend, source } => write!(f, "{} failed to start: {}", backend, source),
+            TtsError::ProcessFailed { backend, status } => {
+                write!(f, "{} exited with status {:?}", backend, status)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TtsError {}
+
+/// One speech-synthesis engine. Mirrors how `tts-rs` abstracts multiple
+/// platform backends behind a single API, except here each backend just
+/// shells out to a CLI tool (consistent with how `ui` drives notifications).
+pub trait TtsBackend: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Whether the backend's binary is actually present on this system.
+    fn is_available(&self) -> bool {
+        which(self.binary())
+    }
+
+    /// The binary this backend shells out to, used for the default
+    /// `is_available` presence check.
+    fn binary(&self) -> &'static str;
+
+    fn speak(&self, text: &str, opts: &SpeakOptions) -> Result<(), TtsError>;
+    fn stop(&self);
+    fn supports_rate(&self) -> bool;
+    fn supports_pitch(&self) -> bool;
+    fn list_voices(&self) -> Vec<String>;
+}
+
+fn which(binary: &str) -> bool {
+    Command::new("which")
+        .arg(binary)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false)
+}
+
+/// speech-dispatcher (`spd-say`) — the default on most desktop Linux setups.
+pub struct SpeechDispatcherBackend;
+
+impl TtsBackend for SpeechDispatcherBackend {
+    fn name(&self) -> &'static str {
+        "speech-dispatcher"
+    }
+
+    fn binary(&self) -> &'static str {
+        "spd-say"
+    }
+
+    fn speak(&self, text: &str, opts: &SpeakOptions) -> Result<(), TtsError> {
+        let mut cmd = Command::new(self.binary());
+        if let Some(rate) = opts.rate {
+            cmd.arg("-r").arg(rate.to_string());
+        }
+        if let Some(pitch) = opts.pitch {
+            cmd.arg("-p").arg(pitch.to_string());
+        }
+        if let Some(voice) = &opts.voice {
+            cmd.arg("-t").arg(voice);
+        } else if let Some(lang) = &opts.lang {
+            cmd.arg("-l").arg(lang);
+        }
+        run_to_completion(cmd.arg(text), self.name())
+    }
+
+    fn stop(&self) {
+        let _ = Command::new("spd-say").arg("-S").stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null()).status();
+    }
+
+    fn supports_rate(&self) -> bool {
+        true
+    }
+
+    fn supports_pitch(&self) -> bool {
+        true
+    }
+
+    fn list_voices(&self) -> Vec<String> {
+        list_lines("spd-say", &["-L"])
+    }
+}
+
+/// espeak-ng — widely available fallback, no speech-dispatcher daemon required.
+pub struct EspeakNgBackend;
+
+impl TtsBackend for EspeakNgBackend {
+    fn name(&self) -> &'static str {
+        "espeak-ng"
+    }
+
+    fn binary(&self) -> &'static str {
+        "espeak-ng"
+    }
+
+    fn speak(&self, text: &str, opts: &SpeakOptions) -> Result<(), TtsError> {
+        let mut cmd = Command::new(self.binary());
+        if let Some(rate) = opts.rate {
+            // espeak-ng's `-s` is words-per-minute; clamp our -100..100 scale
+            // into something in its usable range instead of passing it raw.
+            let wpm = (160 + rate * 2).clamp(20, 400);
+            cmd.arg("-s").arg(wpm.to_string());
+        }
+        if let Some(pitch) = opts.pitch {
+            let scaled = (pitch + 100).clamp(0, 99);
+            cmd.arg("-p").arg(scaled.to_string());
+        }
+        if let Some(voice) = &opts.voice {
+            cmd.arg("-v").arg(voice);
+        } else if let Some(lang) = &opts.lang {
+            // espeak-ng's `-v` accepts a bare language code (e.g. `es`) as a
+            // voice name, but not a region-qualified BCP-47 tag like `es-ES`,
+            // so only the language subtag before the first `-` is passed.
+            let bare = lang.split('-').next().unwrap_or(lang);
+            cmd.arg("-v").arg(bare);
+        }
+        run_to_completion(cmd.arg(text), self.name())
+    }
+
+    fn stop(&self) {
+        let _ = Command::new("pkill").arg("-f").arg("espeak-ng").stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null()).status();
+    }
+
+    fn supports_rate(&self) -> bool {
+        true
+    }
+
+    fn supports_pitch(&self) -> bool {
+        true
+    }
+
+    fn list_voices(&self) -> Vec<String> {
+        list_lines("espeak-ng", &["--voices"])
+            .into_iter()
+            .skip(1) // header row
+            .filter_map(|line| line.split_whitespace().nth(3).map(str::to_string))
+            .collect()
+    }
+}
+
+/// Platform-default engine. Only macOS has a universally-installed one
+/// (`say`); elsewhere there's no single "native" binary to assume, so this
+/// backend honestly reports itself unavailable rather than guessing.
+pub struct NativeBackend;
+
+impl TtsBackend for NativeBackend {
+    fn name(&self) -> &'static str {
+        "native"
+    }
+
+    fn binary(&self) -> &'static str {
+        "say"
+    }
+
+    fn is_available(&self) -> bool {
+        cfg!(target_os = "macos") && which(self.binary())
+    }
+
+    fn speak(&self, text: &str, opts: &SpeakOptions) -> Result<(), TtsError> {
+        let mut cmd = Command::new(self.binary());
+        if let Some(rate) = opts.rate {
+            let wpm = (175 + rate).clamp(90, 360);
+            cmd.arg("-r").arg(wpm.to_string());
+        }
+        if let Some(voice) = &opts.voice {
+            cmd.arg("-v").arg(voice);
+        }
+        // `say` has no flag for "speak this language", only named voices, so
+        // `opts.lang` is intentionally left unused here.
+        run_to_completion(cmd.arg(text), self.name())
+    }
+
+    fn stop(&self) {
+        let _ = Command::new("pkill").arg("-f").arg("say ").stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null()).status();
+    }
+
+    fn supports_rate(&self) -> bool {
+        true
+    }
+
+    fn supports_pitch(&self) -> bool {
+        false
+    }
+
+    fn list_voices(&self) -> Vec<String> {
+        list_lines("say", &["-v", "?"])
+            .into_iter()
+            .filter_map(|line| line.split_whitespace().next().map(str::to_string))
+            .collect()
+    }
+}
+
+fn run_to_completion(cmd: &mut std::process::Command, backend: &'static str) -> Result<(), TtsError> {
+    let status = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map_err(|source| TtsError::SpawnFailed { backend, source })?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(TtsError::ProcessFailed { backend, status: status.code() })
+    }
+}
+
+fn list_lines(binary: &str, args: &[&str]) -> Vec<String> {
+    Command::new(binary)
+        .args(args)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .map(|o| {
+            String::from_utf8_lossy(&o.stdout)
+                .lines()
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Builds the backend fallback chain: the configured `engine` first (if
+/// recognized), then the rest in a fixed, sensible order.
+fn build_backends(preferred: Option<&str>) -> Vec<Box<dyn TtsBackend>> {
+    let mut all: Vec<Box<dyn TtsBackend>> = vec![
+        Box::new(SpeechDispatcherBackend),
+        Box::new(EspeakNgBackend),
+        Box::new(NativeBackend),
+    ];
+    if let Some(name) = preferred {
+        if let Some(pos) = all.iter().position(|b| b.name() == name) {
+            let chosen = all.remove(pos);
+            all.insert(0, chosen);
+        }
+    }
+    all
+}
+
+/// Speaks `text` on a background thread using the configured engine,
+/// falling back through the remaining backends (in order) if the
+/// preferred one isn't installed or fails outright.
+pub fn speak_async(text: String, cfg: crate::config::SpeechOutputCfg, lang: Option<String>) {
+    if !cfg.enabled || text.trim().is_empty() {
+        return;
+    }
+
+    std::thread::spawn(move || {
+        let opts = SpeakOptions {
+            voice: cfg.voice.clone(),
+            rate: cfg.rate,
+            pitch: cfg.pitch,
+            lang,
+        };
+        let backends = build_backends(cfg.engine.as_deref());
+
+        let mut tried_any = false;
+        for backend in &backends {
+            if !backend.is_available() {
+                continue;
+            }
+            tried_any = true;
+            match backend.speak(&text, &opts) {
+                Ok(()) => return,
+                Err(e) => eprintln!("tts: {} failed ({}), trying next backend", backend.name(), e),
+            }
+        }
+
+        if !tried_any {
+            eprintln!("tts: no TTS backend is installed (tried speech-dispatcher, espeak-ng, native)");
+        } else {
+            eprintln!("tts: all available backends failed to speak the answer");
+        }
+    });
+}
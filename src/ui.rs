@@ -135,21 +135,49 @@ pub fn notify_answer_with_open_in_browser(
     });
 }
 
-pub fn notify_confirm_actions(enabled: bool, request_id: &str, title: &str, body: &str) {
-    if !enabled { return; }
-    let request_id = request_id.to_string();
+/// Posts an interactive "Confirm"/"Cancel" notification, the same
+/// `notify-send --action` + read-the-selected-action pattern as
+/// `notify_answer_with_open_in_browser`. `on_action` is called from the
+/// notification thread with `true` for Confirm and `false` for
+/// Cancel/dismiss/error — callers post the result straight into the event
+/// loop instead of relying on an external helper to write a spool file.
+pub fn notify_confirm_actions<F>(enabled: bool, title: &str, body: &str, on_action: F)
+where
+    F: FnOnce(bool) + Send + 'static,
+{
+    if !enabled {
+        return;
+    }
     let title = title.to_string();
     let body = body.to_string();
     std::thread::spawn(move || {
-        // Use a small helper that can use dunstify actions when available.
-        let helper = "./scripts/btwd-notify-confirm.sh";
-        let _ = Command::new(helper)
-            .arg(&request_id)
+        let output = Command::new("notify-send")
             .arg(&title)
             .arg(&body)
+            .arg("--action").arg("confirm=Confirm")
+            .arg("--action").arg("cancel=Cancel")
+            .arg("-u").arg("critical")
+            .arg("-h").arg("string:x-canonical-private-synchronous:btwd-confirm")
             .stdin(Stdio::null())
-            .stdout(Stdio::null())
             .stderr(Stdio::null())
-            .status();
+            .output();
+
+        let output = match output {
+            Ok(o) => o,
+            Err(e) => {
+                eprintln!("notify-send error: {}", e);
+                on_action(false);
+                return;
+            }
+        };
+
+        if !output.status.success() {
+            eprintln!("notify-send failed: status={:?}", output.status.code());
+            on_action(false);
+            return;
+        }
+
+        let selection = String::from_utf8_lossy(&output.stdout);
+        on_action(selection.trim() == "confirm");
     });
 }